@@ -0,0 +1,124 @@
+// Compares the predecessor-map search in aoclib::dijkstra against the previous approach of keeping
+// a full cloned path for every discovered node. Run with `cargo bench --bench dijkstra`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use nonempty::NonEmpty;
+
+use aoclib::dijkstra::{dijkstra, DijkstraNode, DijkstraPath};
+use aoclib::grid::{Grid, UPoint};
+
+const REPEATS: usize = 5;
+
+// The search as it was before: every relaxation clones the whole path to the current node
+fn dijkstra_cloning_paths<F>(
+    start: &UPoint,
+    end: &UPoint,
+    neighbors: F,
+) -> Option<DijkstraPath<UPoint>>
+where
+    F: Fn(&UPoint) -> Vec<(UPoint, usize)>,
+{
+    let mut queue = BinaryHeap::new();
+    let mut dists = HashMap::<UPoint, DijkstraPath<UPoint>>::new();
+
+    let s = DijkstraNode {
+        pos: *start,
+        dist: 0,
+    };
+    queue.push(Reverse((0, *start)));
+    dists.insert(
+        *start,
+        DijkstraPath {
+            path: NonEmpty::new(s),
+        },
+    );
+
+    while let Some(Reverse((dist, pos))) = queue.pop() {
+        if pos == *end {
+            break;
+        }
+        if dists[&pos].dist() < dist {
+            continue;
+        }
+        for (next, distance_to_next) in neighbors(&pos) {
+            let next_node = DijkstraNode {
+                pos: next,
+                dist: dist + distance_to_next,
+            };
+            if dists
+                .get(&next)
+                .map(|pd| next_node.dist < pd.dist())
+                .unwrap_or(true)
+            {
+                let mut new_path = dists[&pos].clone();
+                new_path.path.push(next_node);
+                queue.push(Reverse((next_node.dist, next)));
+                dists.insert(next, new_path);
+            }
+        }
+    }
+    dists.remove(end)
+}
+
+// An open grid with no walls
+fn open_grid(size: usize) -> Grid<bool> {
+    Grid::new(size, size, true)
+}
+
+// A single corridor snaking back and forth across the grid, so the path is about size^2 / 2 long
+fn serpentine(size: usize) -> Grid<bool> {
+    Grid::from_fn(size, size, |p: UPoint| {
+        let row = p.y;
+        row.is_multiple_of(2) || (row % 4 == 1 && p.x == size - 1) || (row % 4 == 3 && p.x == 0)
+    })
+}
+
+fn time<R>(f: impl Fn() -> R) -> Duration {
+    (0..REPEATS)
+        .map(|_| {
+            let t = Instant::now();
+            black_box(f());
+            t.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn compare(name: &str, grid: &Grid<bool>) {
+    let start: UPoint = (0, 0).into();
+    let end: UPoint = (grid.width() - 1, grid.height() - 1).into();
+    let neighbors = |p: &UPoint| {
+        grid.neighbors(*p)
+            .into_iter()
+            .filter(|q| grid[*q])
+            .map(|q| (q, 1))
+            .collect::<Vec<_>>()
+    };
+
+    let expected = dijkstra_cloning_paths(&start, &end, neighbors).map(|p| p.dist());
+    let found = dijkstra(&start, &end, neighbors).dist(&end);
+    assert_eq!(expected, found, "{name}: implementations disagree");
+
+    let cloning = time(|| dijkstra_cloning_paths(&start, &end, neighbors));
+    let preds = time(|| dijkstra(&start, &end, neighbors).get(&end));
+    println!(
+        "{name:<24} dist {:>6}  cloned paths {:>10.2?}  predecessor map {:>10.2?}  ({:.1}x)",
+        found.unwrap(),
+        cloning,
+        preds,
+        cloning.as_secs_f64() / preds.as_secs_f64()
+    );
+}
+
+fn main() {
+    for size in [100, 200, 300] {
+        compare(&format!("open {size}x{size}"), &open_grid(size));
+    }
+    for size in [51, 101] {
+        compare(&format!("serpentine {size}x{size}"), &serpentine(size));
+    }
+}
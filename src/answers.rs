@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::error::AocError;
+use crate::solution::Part;
+
+// The answers recorded for each input, keyed by the input's file name: "DD" for the puzzle input
+// of day DD and "DD_example" for its example. Each entry holds an optional answer per part:
+//
+//     [01_example]
+//     part1 = 11
+//     part2 = "31"
+//
+// Answers are compared as text, so anything too big for a TOML integer can be written as a string.
+// Parts whose puzzle gives a different example from the other part's are listed under skip, since
+// the shared input has no answer for them:
+//
+//     [17_example]
+//     part1 = "4,6,3,5,6,3,5,2,1,0"
+//     skip = [2]
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, InputAnswers>);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InputAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+    #[serde(default)]
+    skip: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, AocError> {
+        toml::from_str(s).map_err(|e| AocError::InvalidInput(e.to_string()))
+    }
+
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        toml::from_str(&AocError::read(path)?)
+            .map_err(|e| AocError::InvalidInput(format!("answers file {path}: {e}")))
+    }
+
+    // The recorded answer for one part of an input, if there is one
+    pub fn get(&self, input: &str, part: Part) -> Option<String> {
+        let answers = self.0.get(input)?;
+        let answer = match part {
+            Part::One => answers.part1.as_ref(),
+            Part::Two => answers.part2.as_ref(),
+        }?;
+        Some(match answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => s.clone(),
+        })
+    }
+
+    // Whether the part isn't checked against this input
+    pub fn skipped(&self, input: &str, part: Part) -> bool {
+        let n = match part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+        self.0.get(input).is_some_and(|a| a.skip.contains(&n))
+    }
+}
+
+// The name of the input file for a day, which is also its key in the answers file
+pub fn input_name(day: u8, example: bool) -> String {
+    if example {
+        format!("{day:02}_example")
+    } else {
+        format!("{day:02}")
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+use crate::params::Settings;
+use crate::solution::{Day, Part};
+
+// What is being timed: parsing the input, or solving one part from the parsed input
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl From<Part> for Step {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Step::Part1,
+            Part::Two => Step::Part2,
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part1 => write!(f, "part 1"),
+            Step::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+// The spread of the times one step took over repeated runs, in nanoseconds so the JSON stays exact
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub input: String,
+    pub step: Step,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Timing {
+    pub fn from_samples(input: &str, step: Step, samples: &[Duration]) -> Self {
+        let mut ns: Vec<u64> = samples.iter().map(|t| t.as_nanos() as u64).collect();
+        ns.sort_unstable();
+        let n = ns.len();
+        assert!(n > 0, "No samples to time");
+        let median = if n % 2 == 1 {
+            ns[n / 2]
+        } else {
+            (ns[n / 2 - 1] + ns[n / 2]) / 2
+        };
+        Timing {
+            input: input.to_string(),
+            step,
+            min_ns: ns[0],
+            median_ns: median,
+            max_ns: ns[n - 1],
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_ns)
+    }
+}
+
+// Run one step of a day `runs` times on an input. Parsing is timed on its own, and each part is
+// timed without its parse, so a part that fails doesn't take the other timings down with it.
+pub fn bench(
+    day: &Day,
+    input_name: &str,
+    input: &str,
+    settings: &Settings,
+    step: Step,
+    runs: usize,
+) -> Result<Timing, AocError> {
+    let parts = match step {
+        Step::Parse => vec![],
+        Step::Part1 => vec![Part::One],
+        Step::Part2 => vec![Part::Two],
+    };
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let outcome = (day.run)(input, settings, &parts)?;
+        samples.push(match outcome.parts.first() {
+            Some(p) => p.elapsed,
+            None => outcome.parse_time,
+        });
+    }
+    Ok(Timing::from_samples(input_name, step, &samples))
+}
+
+// The timings of one benchmark run, as written to and read back from JSON
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub runs: usize,
+    pub timings: Vec<Timing>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("A report is always serializable")
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, AocError> {
+        serde_json::from_str(s).map_err(|e| AocError::InvalidInput(e.to_string()))
+    }
+
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        serde_json::from_str(&AocError::read(path)?)
+            .map_err(|e| AocError::InvalidInput(format!("benchmark file {path}: {e}")))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), AocError> {
+        AocError::write(path, &(self.to_json() + "\n"))
+    }
+
+    pub fn get(&self, input: &str, step: Step) -> Option<&Timing> {
+        self.timings
+            .iter()
+            .find(|t| t.input == input && t.step == step)
+    }
+}
+
+// The change in median time of a step between a baseline and a new run
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub input: String,
+    pub step: Step,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Change {
+    // How much slower the new run is, as a fraction of the baseline: 0.1 is 10% slower
+    pub fn slowdown(&self) -> f64 {
+        self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.slowdown() > threshold
+    }
+}
+
+// Pair every step timed in both reports. Steps missing from the baseline are left out.
+pub fn compare(baseline: &Report, current: &Report) -> Vec<Change> {
+    current
+        .timings
+        .iter()
+        .filter_map(|t| {
+            let before = baseline.get(&t.input, t.step)?;
+            Some(Change {
+                input: t.input.clone(),
+                step: t.step,
+                before: before.median(),
+                after: t.median(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(input: &str, step: Step, median_ns: u64) -> Timing {
+        Timing {
+            input: input.to_string(),
+            step,
+            min_ns: median_ns,
+            median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let ms = |n| Duration::from_millis(n);
+        let t = Timing::from_samples("01", Step::Parse, &[ms(3), ms(1), ms(9)]);
+        assert_eq!((t.min(), t.median(), t.max()), (ms(1), ms(3), ms(9)));
+        let t = Timing::from_samples("01", Step::Parse, &[ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!(t.median(), ms(3));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let report = Report {
+            runs: 5,
+            timings: vec![timing("01", Step::Parse, 10), timing("01", Step::Part2, 20)],
+        };
+        let json = report.to_json();
+        assert!(json.contains("\"step\": \"part2\""));
+        assert_eq!(Report::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let baseline = Report {
+            runs: 1,
+            timings: vec![
+                timing("01", Step::Part1, 100),
+                timing("02", Step::Part1, 100),
+            ],
+        };
+        let current = Report {
+            runs: 1,
+            timings: vec![
+                timing("01", Step::Part1, 105),
+                timing("02", Step::Part1, 150),
+                timing("03", Step::Part1, 100),
+            ],
+        };
+        let changes = compare(&baseline, &current);
+        assert_eq!(changes.len(), 2);
+        let regressed: Vec<&str> = changes
+            .iter()
+            .filter(|c| c.is_regression(0.1))
+            .map(|c| c.input.as_str())
+            .collect();
+        assert_eq!(regressed, vec!["02"]);
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::dijkstra::ShortestPaths;
+use crate::grid::{Grid, UPoint};
+
+// A neighbor as returned by a neighbor factory: either the node itself, as from the grid neighbor
+// helpers, or a (node, cost) pair, as passed to dijkstra. Costs are ignored; every edge counts
+// as one step.
+pub trait IntoNode<T> {
+    fn into_node(self) -> T;
+}
+
+impl<T> IntoNode<T> for T {
+    fn into_node(self) -> T {
+        self
+    }
+}
+
+impl<T, C> IntoNode<T> for (T, C) {
+    fn into_node(self) -> T {
+        self.0
+    }
+}
+
+// BFS for shortest path between start and end
+pub fn bfs<T, N, Neighbors, NeighborFactory>(
+    start: &T,
+    end: &T,
+    neighbors: NeighborFactory,
+) -> ShortestPaths<T>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    N: IntoNode<T>,
+    Neighbors: IntoIterator<Item = N>,
+    NeighborFactory: Fn(&T) -> Neighbors,
+{
+    bfs_by(start, |p| p == end, neighbors)
+}
+
+// BFS for shortest path between start and first node to satisfy predicate end
+pub fn bfs_by<T, N, Neighbors, EndPredicate, NeighborFactory>(
+    start: &T,
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+) -> ShortestPaths<T>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    N: IntoNode<T>,
+    Neighbors: IntoIterator<Item = N>,
+    EndPredicate: Fn(&T) -> bool,
+    NeighborFactory: Fn(&T) -> Neighbors,
+{
+    bfs_multi_by(std::slice::from_ref(start), end, neighbors)
+}
+
+// BFS settling every node reachable from the starts, giving the full distance field
+pub fn bfs_all<T, N, Neighbors, NeighborFactory>(
+    starts: &[T],
+    neighbors: NeighborFactory,
+) -> ShortestPaths<T>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    N: IntoNode<T>,
+    Neighbors: IntoIterator<Item = N>,
+    NeighborFactory: Fn(&T) -> Neighbors,
+{
+    bfs_multi_by(starts, |_| false, neighbors)
+}
+
+// BFS for shortest path between any of the starts and first node to satisfy predicate end. With
+// unit costs the first time a node is seen is along a shortest path, so no priority queue is needed.
+pub fn bfs_multi_by<T, N, Neighbors, EndPredicate, NeighborFactory>(
+    starts: &[T],
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+) -> ShortestPaths<T>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    N: IntoNode<T>,
+    Neighbors: IntoIterator<Item = N>,
+    EndPredicate: Fn(&T) -> bool,
+    NeighborFactory: Fn(&T) -> Neighbors,
+{
+    let mut paths = ShortestPaths::new(starts);
+    let mut queue: VecDeque<T> = starts.iter().copied().collect();
+
+    while let Some(pos) = queue.pop_front() {
+        if end(&pos) {
+            paths.end = Some(pos);
+            break;
+        }
+        let dist = paths.dists[&pos];
+        for next in neighbors(&pos).into_iter().map(IntoNode::into_node) {
+            if let Entry::Vacant(e) = paths.dists.entry(next) {
+                e.insert(dist + 1);
+                paths.preds.insert(next, pos);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+// Every node connected to start through steps from p to q for which same_region(p, q) holds
+pub fn flood_fill<T, N, Neighbors, SameRegion, NeighborFactory>(
+    start: &T,
+    same_region: SameRegion,
+    neighbors: NeighborFactory,
+) -> HashSet<T>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    N: IntoNode<T>,
+    Neighbors: IntoIterator<Item = N>,
+    SameRegion: Fn(&T, &T) -> bool,
+    NeighborFactory: Fn(&T) -> Neighbors,
+{
+    let mut region = HashSet::from([*start]);
+    let mut stack = vec![*start];
+    while let Some(p) = stack.pop() {
+        for q in neighbors(&p).into_iter().map(IntoNode::into_node) {
+            if same_region(&p, &q) && region.insert(q) {
+                stack.push(q);
+            }
+        }
+    }
+    region
+}
+
+// Label every cell of the grid with the index of its connected component, where neighboring cells
+// are connected if same_region holds for their values. Components are numbered from 0 in the order
+// their first cell appears in the grid; returns the labels and the number of components.
+pub fn label_components<T, N, Neighbors, SameRegion, NeighborFactory>(
+    grid: &Grid<T>,
+    same_region: SameRegion,
+    neighbors: NeighborFactory,
+) -> (Grid<usize>, usize)
+where
+    N: IntoNode<UPoint>,
+    Neighbors: IntoIterator<Item = N>,
+    SameRegion: Fn(&T, &T) -> bool,
+    NeighborFactory: Fn(&UPoint) -> Neighbors,
+{
+    let mut labels = Grid::new(grid.width(), grid.height(), usize::MAX);
+    let mut n_components = 0;
+    for p in grid.points() {
+        if labels[p] != usize::MAX {
+            continue;
+        }
+        let component = flood_fill(
+            &p,
+            |a, b| grid.get(*b).is_some_and(|v| same_region(&grid[*a], v)),
+            &neighbors,
+        );
+        for q in component {
+            labels[q] = n_components;
+        }
+        n_components += 1;
+    }
+    (labels, n_components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two rooms split by a wall, the left one with a pillar in it
+    const ROOMS: &str = "\
+....#..
+.#..#..
+....#..
+";
+
+    fn open(grid: &Grid<char>) -> impl Fn(&UPoint) -> Vec<UPoint> + '_ {
+        move |p| {
+            grid.neighbors(*p)
+                .into_iter()
+                .filter(|&q| grid[q] == '.')
+                .collect()
+        }
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let grid: Grid<char> = ROOMS.parse().unwrap();
+        let paths = bfs(&UPoint::new(0, 0), &UPoint::new(2, 2), open(&grid));
+        assert_eq!(paths.end(), Some(UPoint::new(2, 2)));
+        assert_eq!(paths.dist(&UPoint::new(2, 2)), Some(4));
+        assert_eq!(paths.get(&UPoint::new(2, 2)).unwrap().path.len(), 5);
+
+        // The nearest cell of the last column before the wall, found by predicate, is round the
+        // pillar
+        let paths = bfs_by(&UPoint::new(0, 1), |p| p.x == 3, open(&grid));
+        assert_eq!(paths.end().map(|p| paths.dist(&p)), Some(Some(4)));
+
+        // The right room is walled off
+        let paths = bfs(&UPoint::new(0, 0), &UPoint::new(5, 0), open(&grid));
+        assert_eq!(paths.end(), None);
+        assert_eq!(paths.dist(&UPoint::new(5, 0)), None);
+        assert_eq!(paths.dists().count(), 11);
+    }
+
+    #[test]
+    fn multiple_starts() {
+        let grid: Grid<char> = ROOMS.parse().unwrap();
+        let starts = [UPoint::new(0, 0), UPoint::new(3, 0)];
+        let field = bfs_all(&starts, open(&grid));
+        for (p, &d) in field.dists() {
+            let nearest = starts.iter().map(|s| s.manhattan_distance(p)).min();
+            assert_eq!(Some(d), nearest, "cell {p}");
+        }
+        // (2, 2) is 3 steps from the nearer start and 4 from the other one
+        assert_eq!(
+            field.get(&UPoint::new(2, 2)).unwrap().path.first().pos,
+            starts[1]
+        );
+        // (1, 0) and (2, 0) are each one step from a different start
+        for (p, start) in [
+            (UPoint::new(1, 0), starts[0]),
+            (UPoint::new(2, 0), starts[1]),
+        ] {
+            assert_eq!(field.dist(&p), Some(1));
+            assert_eq!(field.get(&p).unwrap().path.first().pos, start);
+        }
+        // (1, 0) is one step from both of these starts, and is reached from one of them
+        let tied = [UPoint::new(0, 0), UPoint::new(2, 0)];
+        let field = bfs_all(&tied, open(&grid));
+        assert_eq!(field.dist(&UPoint::new(1, 0)), Some(1));
+        let start = field.get(&UPoint::new(1, 0)).unwrap().path.first().pos;
+        assert!(tied.contains(&start));
+
+        let paths = bfs_multi_by(&starts, |p| *p == UPoint::new(0, 2), open(&grid));
+        assert_eq!(paths.dist(&UPoint::new(0, 2)), Some(2));
+        assert_eq!(
+            paths.get(&UPoint::new(0, 2)).unwrap().path.first().pos,
+            starts[0]
+        );
+    }
+
+    #[test]
+    fn regions() {
+        let grid: Grid<char> = ROOMS.parse().unwrap();
+        let left = flood_fill(
+            &UPoint::new(0, 0),
+            |_, q| grid[*q] == '.',
+            |p| grid.neighbors(*p),
+        );
+        assert_eq!(left.len(), 11);
+        assert!(!left.contains(&UPoint::new(1, 1)));
+        let wall = flood_fill(
+            &UPoint::new(4, 0),
+            |_, q| grid[*q] == '#',
+            |p| grid.neighbors(*p),
+        );
+        assert_eq!(wall.len(), 3);
+
+        // The left room, the wall, the right room and the pillar, numbered in the order their first
+        // cells come
+        let (labels, n) = label_components(&grid, |a, b| a == b, |p| grid.neighbors(*p));
+        assert_eq!(n, 4);
+        assert_eq!(labels[UPoint::new(0, 0)], 0);
+        assert_eq!(labels[UPoint::new(1, 1)], 3);
+        assert_eq!(labels[UPoint::new(4, 2)], 1);
+        assert_eq!(labels[UPoint::new(6, 2)], 2);
+        assert_eq!(labels.positions(|&l| l == 0).count(), 11);
+    }
+}
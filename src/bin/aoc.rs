@@ -0,0 +1,510 @@
+use std::error::Error;
+use std::panic::catch_unwind;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoclib::answers::{input_name, Answers};
+use aoclib::bench::{self, Report, Step};
+use aoclib::days::{self, DAYS};
+use aoclib::error::AocError;
+use aoclib::frames::Recorder;
+use aoclib::params::{self, Profile, Settings};
+use aoclib::solution::{Day, Part};
+
+const USAGE: &str = "Usage:
+    aoc run <DD|all> [--part 1|2] [--input <path>] [--show] [--record <path> [--every N]]
+                     [--format text|json]
+    aoc check <DD|all> [--part 1|2] [--example] [--answers <path>]
+    aoc bench <DD|all> [--part 1|2] [--example] [--runs N] [--json <path>]
+                       [--baseline <path>] [--threshold <percent>]
+
+Every command also takes --param <name>=<value>, repeatable, to override a puzzle constant.";
+
+const ANSWERS: &str = "answers.toml";
+const RUNS: usize = 10;
+// Percentage by which a step's median time may grow over the baseline before it counts as a regression
+const THRESHOLD: f64 = 10.0;
+
+enum Command {
+    Run,
+    Check,
+    Bench,
+}
+
+// How `aoc run` prints answers: the days' own sentences, or one JSON record per answer for scripts
+#[derive(PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+enum Selection {
+    All,
+    One(u8),
+}
+
+struct Args {
+    command: Command,
+    days: Selection,
+    parts: Vec<Part>,
+    overrides: Vec<(String, String)>,
+    input: Option<String>,
+    show: bool,
+    record: Option<String>,
+    every: usize,
+    format: Format,
+    example: bool,
+    answers: String,
+    runs: usize,
+    json: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, Box<dyn Error>> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("check") => Command::Check,
+        Some("bench") => Command::Bench,
+        Some(cmd) => return Err(format!("Unknown command: {cmd}").into()),
+        None => return Err("No command given".into()),
+    };
+    let days = match args.next().as_deref() {
+        Some("all") => Selection::All,
+        Some(d) => Selection::One(d.parse().map_err(|_| format!("Invalid day: {d}"))?),
+        None => return Err("No day given".into()),
+    };
+    let mut parts = Part::BOTH.to_vec();
+    let mut overrides = Vec::new();
+    let mut input = None;
+    let mut show = false;
+    let mut record = None;
+    let mut every = 1;
+    let mut format = Format::Text;
+    let mut example = false;
+    let mut answers = ANSWERS.to_string();
+    let mut runs = RUNS;
+    let mut json = None;
+    let mut baseline = None;
+    let mut threshold = THRESHOLD;
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {flag}"));
+        match (&command, flag.as_str()) {
+            (_, "--part") => parts = vec![value()?.parse()?],
+            (_, "--param") => overrides.push(params::parse_override(&value()?)?),
+            (Command::Run, "--input") => input = Some(value()?),
+            (Command::Run, "--show") => show = true,
+            (Command::Run, "--record") => record = Some(value()?),
+            (Command::Run, "--every") => every = value()?.parse()?,
+            (Command::Run, "--format") => {
+                format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    f => return Err(format!("Unknown format: {f} (expected text or json)").into()),
+                }
+            }
+            (Command::Check | Command::Bench, "--example") => example = true,
+            (Command::Check, "--answers") => answers = value()?,
+            (Command::Bench, "--runs") => runs = value()?.parse()?,
+            (Command::Bench, "--json") => json = Some(value()?),
+            (Command::Bench, "--baseline") => baseline = Some(value()?),
+            (Command::Bench, "--threshold") => threshold = value()?.parse()?,
+            _ => return Err(format!("Unknown option: {flag}").into()),
+        }
+    }
+    if input.is_some() && matches!(days, Selection::All) {
+        return Err("--input can only be used when running a single day".into());
+    }
+    if record.is_some() && matches!(days, Selection::All) {
+        return Err("--record can only be used when running a single day".into());
+    }
+    if show && format == Format::Json {
+        return Err("--show can't be combined with --format json".into());
+    }
+    if every == 0 {
+        return Err("--every must be at least 1".into());
+    }
+    if runs == 0 {
+        return Err("--runs must be at least 1".into());
+    }
+    Ok(Args {
+        command,
+        days,
+        parts,
+        overrides,
+        input,
+        show,
+        record,
+        every,
+        format,
+        example,
+        answers,
+        runs,
+        json,
+        baseline,
+        threshold,
+    })
+}
+
+impl Args {
+    // The parameter values for an input, in the profile its name implies
+    fn settings(&self, input_name: &str) -> Settings {
+        Settings {
+            profile: Profile::for_input(input_name),
+            overrides: self.overrides.clone(),
+        }
+    }
+}
+
+fn run_day(day: &Day, input_path: &str, args: &Args) -> Result<(), AocError> {
+    let input = AocError::read(input_path)?;
+    let settings = args.settings(input_path);
+    let outcome = (day.run)(&input, &settings, &args.parts)?;
+    for p in outcome.parts {
+        match args.format {
+            Format::Text => println!("{}", p.report),
+            Format::Json => println!(
+                "{}",
+                serde_json::to_string(&p.record(day.day)).expect("A record is always serializable")
+            ),
+        }
+    }
+    if args.show {
+        match (day.draw)(&input, &settings)? {
+            Some(canvas) => canvas.print(),
+            None => eprintln!("Day {:02} has nothing to show", day.day),
+        }
+    }
+    if let Some(path) = &args.record {
+        let mut frames = Recorder::create(path)?.every(args.every);
+        (day.animate)(&input, &settings, &mut frames)?;
+        match frames.finish()? {
+            0 => eprintln!("Day {:02} has no simulation to record", day.day),
+            // Keep stdout to the records when it is being read by a script
+            n if args.format == Format::Json => eprintln!("Recorded {n} frames to {path}"),
+            n => println!("Recorded {n} frames to {path}"),
+        }
+    }
+    Ok(())
+}
+
+fn run(args: &Args, selected: &[&Day]) -> ExitCode {
+    let mut status = ExitCode::SUCCESS;
+    for day in selected {
+        let input_path = args
+            .input
+            .clone()
+            .unwrap_or_else(|| format!("input/{:02}", day.day));
+        if args.format == Format::Text {
+            println!("--- Day {:02} ---", day.day);
+        }
+        if let Err(e) = run_day(day, &input_path, args) {
+            eprintln!("Day {:02} failed: {e}", day.day);
+            status = ExitCode::FAILURE;
+        }
+    }
+    status
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    // No answer has been recorded for this part yet
+    New,
+    Error,
+    // The part hasn't been solved yet
+    Unsolved,
+    // The input file is missing, or answers.toml says not to check the part against it
+    Skip,
+}
+
+impl Verdict {
+    fn label(self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail => "FAIL",
+            Verdict::New => "new",
+            Verdict::Error => "ERROR",
+            Verdict::Unsolved => "unsolved",
+            Verdict::Skip => "skip",
+        }
+    }
+}
+
+struct CheckRow {
+    day: u8,
+    part: Option<Part>,
+    expected: String,
+    answer: String,
+    verdict: Verdict,
+    elapsed: Option<Duration>,
+}
+
+fn check_part(
+    day: &Day,
+    input: &str,
+    settings: &Settings,
+    part: Part,
+    expected: Option<String>,
+) -> CheckRow {
+    // A panicking day shouldn't stop the remaining days from being checked
+    let outcome = catch_unwind(|| (day.run)(input, settings, &[part]));
+    let (answer, elapsed, verdict) = match outcome {
+        Ok(Ok(outcome)) => {
+            let p = &outcome.parts[0];
+            let verdict = match &expected {
+                Some(e) if *e == p.answer => Verdict::Pass,
+                Some(_) => Verdict::Fail,
+                None => Verdict::New,
+            };
+            (p.answer.clone(), Some(p.elapsed), verdict)
+        }
+        Ok(Err(e @ AocError::Unsolved)) => (e.to_string(), None, Verdict::Unsolved),
+        Ok(Err(e)) => (e.to_string(), None, Verdict::Error),
+        Err(_) => ("panicked".to_string(), None, Verdict::Error),
+    };
+    CheckRow {
+        day: day.day,
+        part: Some(part),
+        expected: expected.unwrap_or_default(),
+        answer,
+        verdict,
+        elapsed,
+    }
+}
+
+fn check_day(day: &Day, args: &Args, answers: &Answers) -> Vec<CheckRow> {
+    let name = input_name(day.day, args.example);
+    let input_path = format!("input/{name}");
+    let Ok(input) = std::fs::read_to_string(&input_path) else {
+        return vec![CheckRow {
+            day: day.day,
+            part: None,
+            expected: String::new(),
+            answer: format!("no {input_path}"),
+            verdict: Verdict::Skip,
+            elapsed: None,
+        }];
+    };
+    let settings = args.settings(&name);
+    args.parts
+        .iter()
+        .map(|&part| {
+            if answers.skipped(&name, part) {
+                return CheckRow {
+                    day: day.day,
+                    part: Some(part),
+                    expected: String::new(),
+                    answer: format!("not checked against {input_path}"),
+                    verdict: Verdict::Skip,
+                    elapsed: None,
+                };
+            }
+            check_part(day, &input, &settings, part, answers.get(&name, part))
+        })
+        .collect()
+}
+
+fn print_table(header: &[&str], cells: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|c| c[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+    let line = |row: &[&str]| {
+        let padded: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:<w$}"))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(header);
+    for c in cells {
+        line(&c.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+}
+
+fn check(args: &Args, selected: &[&Day]) -> ExitCode {
+    let answers = match Answers::load(&args.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let rows: Vec<CheckRow> = selected
+        .iter()
+        .flat_map(|day| check_day(day, args, &answers))
+        .collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|r| {
+            vec![
+                format!("{:02}", r.day),
+                r.part.map(|p| p.to_string()).unwrap_or_default(),
+                r.expected.clone(),
+                r.answer.clone(),
+                r.verdict.label().to_string(),
+                r.elapsed.map(|t| format!("{t:.2?}")).unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(
+        &["Day", "Part", "Expected", "Answer", "Result", "Time"],
+        &cells,
+    );
+
+    let count = |v: Verdict| rows.iter().filter(|r| r.verdict == v).count();
+    println!(
+        "\n{} passed, {} failed, {} errors, {} new, {} unsolved, {} skipped",
+        count(Verdict::Pass),
+        count(Verdict::Fail),
+        count(Verdict::Error),
+        count(Verdict::New),
+        count(Verdict::Unsolved),
+        count(Verdict::Skip)
+    );
+    if count(Verdict::Fail) + count(Verdict::Error) > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench(args: &Args, selected: &[&Day]) -> ExitCode {
+    let baseline = match args.baseline.as_deref().map(Report::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut status = ExitCode::SUCCESS;
+    let mut report = Report {
+        runs: args.runs,
+        timings: Vec::new(),
+    };
+    for day in selected {
+        let name = input_name(day.day, args.example);
+        let input_path = format!("input/{name}");
+        let input = match AocError::read(&input_path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {:02} failed: {e}", day.day);
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let steps = std::iter::once(Step::Parse).chain(args.parts.iter().map(|&p| p.into()));
+        for step in steps {
+            match bench::bench(day, &name, &input, &args.settings(&name), step, args.runs) {
+                Ok(timing) => report.timings.push(timing),
+                Err(e) => {
+                    eprintln!("Day {:02} {step} failed: {e}", day.day);
+                    status = ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    let changes = baseline
+        .as_ref()
+        .map(|b| bench::compare(b, &report))
+        .unwrap_or_default();
+    let threshold = args.threshold / 100.0;
+    let mut header = vec!["Input", "Step", "Min", "Median", "Max"];
+    if baseline.is_some() {
+        header.extend(["Baseline", "Change"]);
+    }
+    let cells: Vec<Vec<String>> = report
+        .timings
+        .iter()
+        .map(|t| {
+            let mut row = vec![
+                t.input.clone(),
+                t.step.to_string(),
+                format!("{:.2?}", t.min()),
+                format!("{:.2?}", t.median()),
+                format!("{:.2?}", t.max()),
+            ];
+            if baseline.is_some() {
+                match changes
+                    .iter()
+                    .find(|c| c.input == t.input && c.step == t.step)
+                {
+                    Some(c) => row.extend([
+                        format!("{:.2?}", c.before),
+                        format!(
+                            "{:+.1}%{}",
+                            c.slowdown() * 100.0,
+                            if c.is_regression(threshold) {
+                                "  REGRESSION"
+                            } else {
+                                ""
+                            }
+                        ),
+                    ]),
+                    None => row.extend([String::new(), "new".to_string()]),
+                }
+            }
+            row
+        })
+        .collect();
+    print_table(&header, &cells);
+
+    if let Some(path) = &args.json {
+        if let Err(e) = report.save(path) {
+            eprintln!("{e}");
+            status = ExitCode::FAILURE;
+        }
+    }
+    let regressions = changes
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+    if baseline.is_some() {
+        println!(
+            "\n{regressions} of {} steps regressed by more than {}%",
+            changes.len(),
+            args.threshold
+        );
+    }
+    if regressions > 0 {
+        status = ExitCode::FAILURE;
+    }
+    status
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let selected: Vec<&Day> = match args.days {
+        Selection::All => DAYS.iter().collect(),
+        Selection::One(d) => match days::get(d) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {d} is not solved");
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    match args.command {
+        Command::Run => run(&args, &selected),
+        Command::Check => check(&args, &selected),
+        Command::Bench => bench(&args, &selected),
+    }
+}
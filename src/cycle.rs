@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where the states x, step(x), step(step(x)), ... of a simulation start repeating: after prefix
+// states the next length states repeat forever, starting with entry. A simulation that stops can
+// be modelled as one whose final state steps to itself, making a cycle of length 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<T> {
+    pub prefix: usize,
+    pub length: usize,
+    pub entry: T,
+}
+
+impl<T: Clone> Cycle<T> {
+    // The earliest step with the same state as step n
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.length
+        }
+    }
+
+    // The state after n steps from start, taking at most prefix + length steps however large n is
+    pub fn nth<F: FnMut(&T) -> T>(&self, start: &T, mut step: F, n: usize) -> T {
+        let mut x = start.clone();
+        for _ in 0..self.equivalent_step(n) {
+            x = step(&x);
+        }
+        x
+    }
+}
+
+// Brent's algorithm, which only ever keeps two states but runs the simulation about three times
+// over. Best for big states or long cycles.
+pub fn brent<T, F>(start: &T, mut step: F) -> Cycle<T>
+where
+    T: Clone + Eq,
+    F: FnMut(&T) -> T,
+{
+    // Find the cycle length by moving the tortoise up to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare a cycle length ahead, the two meet where the cycle starts
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    Cycle {
+        prefix,
+        length,
+        entry: tortoise,
+    }
+}
+
+// Remember every state seen until one comes round again. Runs the simulation only once, but keeps
+// all of its states.
+pub fn hashing<T, F>(start: &T, mut step: F) -> Cycle<T>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut x = start.clone();
+    for i in 0.. {
+        if let Some(&prefix) = seen.get(&x) {
+            return Cycle {
+                prefix,
+                length: i - prefix,
+                entry: x,
+            };
+        }
+        let next = step(&x);
+        seen.insert(x, i);
+        x = next;
+    }
+    unreachable!("A cycle is always found before running out of steps")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+    fn rho(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn both_methods_agree() {
+        let expected = Cycle {
+            prefix: 3,
+            length: 4,
+            entry: 3,
+        };
+        assert_eq!(brent(&0, rho), expected);
+        assert_eq!(hashing(&0, rho), expected);
+        assert_eq!(brent(&5, rho).prefix, 0);
+    }
+
+    #[test]
+    fn fixed_point() {
+        let stop = |x: &u32| (*x + 1).min(10);
+        let cycle = brent(&0, stop);
+        assert_eq!((cycle.prefix, cycle.length, cycle.entry), (10, 1, 10));
+        assert_eq!(hashing(&0, stop), cycle);
+    }
+
+    #[test]
+    fn jumps_ahead() {
+        let cycle = hashing(&0, rho);
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(7), 3);
+        assert_eq!(
+            cycle.nth(&0, rho, 1_000_000_000),
+            3 + (1_000_000_000 - 3) % 4
+        );
+        // A linear congruential generator mod 1000, stepped far beyond its period
+        let lcg = |x: &u64| (x * 21 + 7) % 1000;
+        let cycle = brent(&1, lcg);
+        let slow = (0..5000).fold(1, |x, _| lcg(&x));
+        assert_eq!(cycle.nth(&1, lcg, 5000), slow);
+    }
+}
@@ -0,0 +1,110 @@
+use regex::Regex;
+use std::iter::zip;
+
+use std::collections::HashMap;
+
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+fn parse_line(s: &str) -> Option<(i32, i32)> {
+    let r = Regex::new(r"^(\d+)\s+(\d+)$").ok()?;
+    let caps = r.captures(s)?;
+    let a = caps.get(1)?.as_str().parse().ok();
+    let b = caps.get(2)?.as_str().parse().ok();
+    a.zip(b)
+}
+
+fn sorted_unstable<T: Clone + Ord>(vec: &[T]) -> Vec<T> {
+    let mut out = vec.to_vec();
+    out.sort_unstable();
+    out
+}
+
+pub fn distance(in_a: &[i32], in_b: &[i32]) -> i32 {
+    let va = sorted_unstable(in_a);
+    let vb = sorted_unstable(in_b);
+
+    let mut distance = 0;
+    for (a, b) in zip(va, vb) {
+        distance += (a - b).abs();
+    }
+    distance
+}
+
+pub fn similarity_score(in_a: &[i32], in_b: &[i32]) -> i32 {
+    let mut counts = HashMap::<i32, i32>::new();
+    let mut score = 0;
+    for b in in_b {
+        counts.insert(*b, counts.get(b).copied().unwrap_or(0) + 1);
+    }
+    for a in in_a {
+        score += a * counts.get(a).copied().unwrap_or(0);
+    }
+    score
+}
+
+impl Solution for Day01 {
+    type Input<'a> = (Vec<i32>, Vec<i32>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let mut va: Vec<i32> = Vec::new();
+        let mut vb: Vec<i32> = Vec::new();
+
+        for line in input.lines() {
+            parse_line(line)
+                .map(|(a, b)| {
+                    va.push(a);
+                    vb.push(b);
+                })
+                .ok_or_else(|| AocError::InvalidInput(format!("could not parse line {line:?}")))?;
+        }
+        Ok((va, vb))
+    }
+
+    fn part1((va, vb): &Self::Input<'_>) -> Result<i32, AocError> {
+        Ok(distance(va, vb))
+    }
+
+    fn part2((va, vb): &Self::Input<'_>) -> Result<i32, AocError> {
+        Ok(similarity_score(va, vb))
+    }
+
+    fn report1(dist: &i32) -> String {
+        format!("Distance: {}", dist)
+    }
+
+    fn report2(sim_score: &i32) -> String {
+        format!("Similarity score: {}", sim_score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day01::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day01::part1(&input).unwrap(), 11);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day01::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day01::part2(&input).unwrap(), 31);
+    }
+}
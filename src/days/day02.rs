@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day02;
+
+fn within_tolerance(prev: i32, new: i32) -> bool {
+    let d = (prev - new).abs();
+    (1..=3).contains(&d)
+}
+
+pub fn is_safe(log: &[i32], dampener: bool) -> bool {
+    let mut ord: Option<Ordering> = None;
+    let mut prev: Option<i32> = None;
+
+    for (i, cur) in log.iter().enumerate() {
+        let tolerated = prev.map(|x| within_tolerance(x, *cur)).unwrap_or(true);
+        let correct_order = prev.zip(ord).map(|(p, o)| cur.cmp(&p) == o).unwrap_or(true);
+        if !(tolerated && correct_order) {
+            if !dampener {
+                return false;
+            } else {
+                for k in 0..(i + 1) {
+                    /* Three values are involved in accepting the current level: the current level,
+                     * the previous level, and the level before that. This is because we need
+                     *     sign(x-y) == sign(y-z).
+                     * If the current level is rejected, we retry, in turn, with each of these three values
+                     * dropped.
+                     */
+                    let mut dropped = Vec::new();
+                    dropped.append(&mut log[..i - k].to_vec());
+                    dropped.append(&mut log[i - k + 1..].to_vec());
+                    if is_safe(&dropped, false) {
+                        return true;
+                    }
+                }
+                return false;
+            }
+        }
+        ord = ord.or(prev.map(|x| cur.cmp(&x)));
+        prev = Some(*cur);
+    }
+    true
+}
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Vec<i32>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let mut reports = Vec::new();
+        for line in input.lines() {
+            let mut report: Vec<i32> = Vec::new();
+            for entry in line.split_whitespace() {
+                report.push(entry.parse()?);
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    fn part1(reports: &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(reports.iter().filter(|r| is_safe(r, false)).count())
+    }
+
+    fn part2(reports: &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(reports.iter().filter(|r| is_safe(r, true)).count())
+    }
+
+    fn report1(safe: &usize) -> String {
+        format!("Number of safe reports: {}", safe)
+    }
+
+    fn report2(safe_with_dampener: &usize) -> String {
+        format!(
+            "Number of safe reports with dampener: {}",
+            safe_with_dampener
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day02::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day02::part1(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day02::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day02::part2(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn dampener_drops_one_level() {
+        assert!(!is_safe(&[1, 3, 2, 4, 5], false));
+        assert!(is_safe(&[1, 3, 2, 4, 5], true));
+        assert!(is_safe(&[8, 6, 4, 4, 1], true));
+        // The first level can be the bad one too
+        assert!(is_safe(&[5, 1, 2, 3, 4], true));
+        assert!(!is_safe(&[1, 2, 7, 8, 9], true));
+    }
+}
@@ -0,0 +1,96 @@
+use regex::Regex;
+
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day03;
+
+pub enum Instruction {
+    Mul(i32, i32),
+    Do,
+    Dont,
+}
+
+impl Solution for Day03 {
+    type Input<'a> = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let mul_pattern = Regex::new(r"(mul\((\d{1,3}),(\d{1,3})\))|(do\(\))|(don't\(\))")
+            .expect("The instruction pattern is a valid regex");
+        let mut instructions = Vec::new();
+        for cap in mul_pattern.captures_iter(input) {
+            let s = cap.get(0).unwrap().as_str();
+            if s == "do()" {
+                instructions.push(Instruction::Do);
+            } else if s == "don't()" {
+                instructions.push(Instruction::Dont);
+            } else if s.starts_with("mul(") {
+                let x: i32 = cap.get(2).unwrap().as_str().parse()?;
+                let y: i32 = cap.get(3).unwrap().as_str().parse()?;
+                instructions.push(Instruction::Mul(x, y));
+            }
+        }
+        Ok(instructions)
+    }
+
+    fn part1(instructions: &Self::Input<'_>) -> Result<i32, AocError> {
+        let mut total = 0;
+        for i in instructions {
+            if let Instruction::Mul(x, y) = i {
+                total += x * y;
+            }
+        }
+        Ok(total)
+    }
+
+    fn part2(instructions: &Self::Input<'_>) -> Result<i32, AocError> {
+        let mut only_enabled = 0;
+        let mut enabled = true;
+        for i in instructions {
+            match i {
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
+                Instruction::Mul(x, y) => {
+                    if enabled {
+                        only_enabled += x * y;
+                    }
+                }
+            }
+        }
+        Ok(only_enabled)
+    }
+
+    fn report1(total: &i32) -> String {
+        format!("Result of multiplications summed: {}", total)
+    }
+
+    fn report2(only_enabled: &i32) -> String {
+        format!("Result of enabled multiplications summed: {}", only_enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let example = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let input = Day03::parse_example(example).unwrap();
+        assert_eq!(Day03::part1(&input).unwrap(), 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day03::parse_example(
+            "\
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+",
+        )
+        .unwrap();
+        assert_eq!(Day03::part2(&input).unwrap(), 48);
+    }
+}
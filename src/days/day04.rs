@@ -0,0 +1,101 @@
+use crate::error::AocError;
+use crate::grid::{to_signed, Grid, IPoint, OrdinalDirection};
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day04;
+
+fn search_for_xmas(grid: &Grid<char>, start: IPoint, dp: IPoint) -> bool {
+    let xmas = "XMAS";
+    let mut found = String::from("");
+    let mut p = start;
+    while let Some(l) = grid.get(p) {
+        found.push(*l);
+        p += dp;
+    }
+    found.starts_with(xmas)
+}
+
+fn find_xmas(grid: &Grid<char>, start: IPoint) -> u32 {
+    OrdinalDirection::ALL
+        .into_iter()
+        .filter(|d| search_for_xmas(grid, start, d.delta()))
+        .count() as u32
+}
+
+// The three letters on the line through center in direction d
+fn word_along(grid: &Grid<char>, center: IPoint, d: OrdinalDirection) -> String {
+    [center - d.delta(), center, center + d.delta()]
+        .into_iter()
+        .filter_map(|p| grid.get(p))
+        .collect()
+}
+
+fn is_mas(w: String) -> bool {
+    w == "MAS" || w == "SAM"
+}
+
+fn find_cross_mas(grid: &Grid<char>, center: IPoint) -> bool {
+    let se_word = word_along(grid, center, OrdinalDirection::SouthEast);
+    let ne_word = word_along(grid, center, OrdinalDirection::NorthEast);
+    is_mas(se_word) && is_mas(ne_word)
+}
+
+impl Solution for Day04 {
+    type Input<'a> = Grid<char>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(g: &Self::Input<'_>) -> Result<u32, AocError> {
+        Ok(g.points().map(to_signed).map(|p| find_xmas(g, p)).sum())
+    }
+
+    fn part2(g: &Self::Input<'_>) -> Result<u32, AocError> {
+        Ok(g.points()
+            .map(to_signed)
+            .filter(|p| find_cross_mas(g, *p))
+            .count() as u32)
+    }
+
+    fn report1(xmas_count: &u32) -> String {
+        format!("XMAS count: {}", xmas_count)
+    }
+
+    fn report2(cross_mas_count: &u32) -> String {
+        format!("X-MAS count: {}", cross_mas_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day04::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day04::part1(&input).unwrap(), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day04::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day04::part2(&input).unwrap(), 9);
+    }
+}
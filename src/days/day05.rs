@@ -0,0 +1,154 @@
+use crate::error::AocError;
+use crate::graph::Graph;
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day05;
+
+// The page ordering rules, with an edge from each page to the pages that must come after it
+pub type TopologicalOrder = Graph<u32>;
+
+pub type Update = Vec<u32>;
+
+// The pages of an update in an order that follows the rules. Only the rules between pages in the
+// update apply; taken together, the rules may well go round in circles.
+pub fn sort_topologically(upd: &[u32], order: &TopologicalOrder) -> Result<Update, AocError> {
+    let mut rules = order.induced(upd.iter().copied());
+    for &page in upd {
+        rules.add_node(page);
+    }
+    rules.topological_sort().map_err(|cycle| {
+        AocError::InvalidInput(format!(
+            "the rules for update {upd:?} go round in a circle: {cycle}"
+        ))
+    })
+}
+
+impl Solution for Day05 {
+    type Input<'a> = (TopologicalOrder, Vec<Update>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let mut lines = input.lines();
+        let mut order = TopologicalOrder::new();
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            let mut s = line.split("|");
+            let before = s
+                .next()
+                .ok_or_else(|| AocError::InvalidInput(format!("could not parse line {line:?}")))?
+                .parse()?;
+            let after = s
+                .next()
+                .ok_or_else(|| AocError::InvalidInput(format!("could not parse line {line:?}")))?
+                .parse()?;
+
+            order.add_edge(before, after);
+        }
+
+        let updates = lines
+            .map(|line| line.split(",").filter_map(|s| s.parse().ok()).collect())
+            .collect();
+        Ok((order, updates))
+    }
+
+    fn part1((order, updates): &Self::Input<'_>) -> Result<u32, AocError> {
+        Ok(updates
+            .iter()
+            .filter(|u| order.is_topological_order(u))
+            .map(|u| u[u.len() / 2])
+            .sum())
+    }
+
+    fn part2((order, updates): &Self::Input<'_>) -> Result<u32, AocError> {
+        updates
+            .iter()
+            .filter(|u| !order.is_topological_order(u))
+            .map(|u| sort_topologically(u, order).map(|w| w[w.len() / 2]))
+            .sum()
+    }
+
+    fn report1(page_sum_sorted: &u32) -> String {
+        format!(
+            "Sum of middle pages of correctly sorted updates: {}",
+            page_sum_sorted
+        )
+    }
+
+    fn report2(page_sum_unsorted: &u32) -> String {
+        format!(
+            "Sum of middle pages of incorrectly sorted updates: {}",
+            page_sum_unsorted
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day05::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day05::part1(&input).unwrap(), 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day05::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day05::part2(&input).unwrap(), 123);
+    }
+
+    #[test]
+    fn sorts_updates() {
+        let (order, _) = Day05::parse_example(EXAMPLE).unwrap();
+        assert!(order.is_topological_order(&[75, 47, 61, 53, 29]));
+        assert!(!order.is_topological_order(&[75, 97, 47, 61, 53]));
+        assert_eq!(
+            sort_topologically(&[97, 13, 75, 29, 47], &order).unwrap(),
+            vec![97, 75, 47, 29, 13]
+        );
+    }
+
+    #[test]
+    fn circular_rules() {
+        let (order, updates) = Day05::parse_example("1|2\n2|3\n3|1\n\n1,2\n3,1,2\n").unwrap();
+        // Each rule on its own is fine, but all three at once can't be followed
+        assert!(sort_topologically(&updates[0], &order).is_ok());
+        assert!(Day05::part2(&(order, updates)).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use crate::cycle::{brent, Cycle};
+use crate::error::AocError;
+use crate::frames::{Recorder, Rgb, BLACK, GRAY, RED, YELLOW};
+use crate::grid::{step, CardinalDirection, Grid, UPoint};
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day06;
+
+type Guard = (UPoint, CardinalDirection);
+
+// Where the guard is next, or None once they have left the map
+fn step_guard(map: &Grid<char>, (pos, dir): Guard) -> Option<Guard> {
+    let next = step(pos, dir)?;
+    match map.get(next)? {
+        '#' => Some((pos, dir.clockwise())),
+        _ => Some((next, dir)),
+    }
+}
+
+// Every position and heading of the guard until they leave the map, which never happens if they
+// are stuck in a loop
+fn patrol(guard: Guard, map: &Grid<char>) -> impl Iterator<Item = Guard> + '_ {
+    std::iter::successors(Some(guard), |&g| step_guard(map, g))
+}
+
+// Where the guard's route starts repeating. Leaving the map is a cycle on the None state.
+fn route(guard: Guard, map: &Grid<char>) -> Cycle<Option<Guard>> {
+    brent(&Some(guard), |g| g.and_then(|g| step_guard(map, g)))
+}
+
+// Every state of the guard's route, each exactly once
+fn path(guard: Guard, map: &Grid<char>) -> impl Iterator<Item = Guard> + '_ {
+    let route = route(guard, map);
+    patrol(guard, map).take(route.prefix + route.length)
+}
+
+fn color(c: &char) -> Rgb {
+    match c {
+        '#' => GRAY,
+        'X' => YELLOW,
+        '.' => BLACK,
+        _ => RED,
+    }
+}
+
+pub fn visited_tiles(map: &Grid<char>, pos: UPoint) -> BTreeSet<UPoint> {
+    path((pos, CardinalDirection::North), map)
+        .map(|(p, _)| p)
+        .collect()
+}
+
+impl Solution for Day06 {
+    type Input<'a> = (Grid<char>, UPoint);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let map: Grid<char> = input.parse()?;
+        let pos = map
+            .find(&'^')
+            .ok_or(AocError::MissingMarker("starting position"))?;
+        Ok((map, pos))
+    }
+
+    fn part1((map, pos): &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(visited_tiles(map, *pos).len())
+    }
+
+    fn part2((map, pos): &Self::Input<'_>) -> Result<usize, AocError> {
+        let dir = CardinalDirection::North;
+        let mut loops = 0;
+        let mut modified_map = map.clone();
+        for p in visited_tiles(map, *pos) {
+            let prev = modified_map[p];
+            modified_map[p] = '#';
+            if route((*pos, dir), &modified_map).entry.is_some() {
+                loops += 1;
+            }
+            modified_map[p] = prev;
+        }
+        Ok(loops)
+    }
+
+    // The guard walking their route, leaving a trail behind
+    fn animate((map, pos): &Self::Input<'_>, frames: &mut Recorder) -> Result<(), AocError> {
+        let mut trail = map.clone();
+        for (p, dir) in path((*pos, CardinalDirection::North), map) {
+            trail[p] = dir.into();
+            frames.record(&trail, color)?;
+            trail[p] = 'X';
+        }
+        Ok(())
+    }
+
+    fn report1(n_tiles: &usize) -> String {
+        format!("Number of visited tiles: {}", n_tiles)
+    }
+
+    fn report2(loops: &usize) -> String {
+        format!("Number of loops that can be created: {}", loops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day06::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day06::part1(&input).unwrap(), 41);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day06::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day06::part2(&input).unwrap(), 6);
+    }
+}
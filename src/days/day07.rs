@@ -0,0 +1,130 @@
+use itertools::Itertools;
+use nom::{bytes::complete::tag, sequence::separated_pair, IResult};
+
+use crate::error::AocError;
+use crate::params::Params;
+use crate::parse::{integer, integers, lines, parse_all};
+use crate::solution::Solution;
+
+pub struct Day07;
+
+pub enum Operation {
+    Add,
+    Mul,
+    Concat,
+}
+
+fn concat_numbers(x: u64, y: u64) -> u64 {
+    x * 10u64.pow((y as f64).log10().floor() as u32 + 1) + y
+}
+
+fn calibration_is_valid(target: u64, inputs: &[u64], ops: &[&Operation]) -> bool {
+    let mut inputs = inputs.iter();
+    let Some(acc) = inputs.next() else {
+        return false;
+    };
+    let mut acc: u64 = *acc;
+    for (&v, op) in inputs.zip(ops) {
+        match op {
+            Operation::Add => acc += v,
+            Operation::Mul => acc *= v,
+            Operation::Concat => acc = concat_numbers(acc, v),
+        }
+        if acc > target {
+            break;
+        }
+    }
+    acc == target
+}
+
+pub fn calibration_can_be_valid(target: u64, inputs: &[u64], allowed_ops: &[Operation]) -> bool {
+    itertools::repeat_n(allowed_ops.iter(), inputs.len() - 1)
+        .multi_cartesian_product()
+        .any(|ops| calibration_is_valid(target, inputs, &ops))
+}
+
+fn parse_calibration(s: &str) -> IResult<&str, (u64, Vec<u64>)> {
+    separated_pair(integer, tag(": "), integers(" "))(s)
+}
+
+pub fn total_calibration(calibrations: &[(u64, Vec<u64>)], allowed_ops: &[Operation]) -> u64 {
+    calibrations
+        .iter()
+        .filter(|(target, inputs)| calibration_can_be_valid(*target, inputs, allowed_ops))
+        .map(|(target, _)| target)
+        .sum()
+}
+
+impl Solution for Day07 {
+    type Input<'a> = Vec<(u64, Vec<u64>)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(parse_all(lines(parse_calibration), input)?)
+    }
+
+    fn part1(calibrations: &Self::Input<'_>) -> Result<u64, AocError> {
+        let addmul = vec![Operation::Add, Operation::Mul];
+        Ok(total_calibration(calibrations, &addmul))
+    }
+
+    fn part2(calibrations: &Self::Input<'_>) -> Result<u64, AocError> {
+        let addmulconcat = vec![Operation::Add, Operation::Mul, Operation::Concat];
+        Ok(total_calibration(calibrations, &addmulconcat))
+    }
+
+    fn report1(total: &u64) -> String {
+        format!("Total calibration result (Add, Mul): {}", total)
+    }
+
+    fn report2(total_concat: &u64) -> String {
+        format!(
+            "Total calibration result (Add, Mul, Concat): {}",
+            total_concat
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day07::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day07::part1(&input).unwrap(), 3749);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day07::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day07::part2(&input).unwrap(), 11387);
+    }
+
+    #[test]
+    fn concatenation_is_needed() {
+        let addmul = vec![Operation::Add, Operation::Mul];
+        let addmulconcat = vec![Operation::Add, Operation::Mul, Operation::Concat];
+        assert!(calibration_can_be_valid(190, &[10, 19], &addmul));
+        assert!(!calibration_can_be_valid(7290, &[6, 8, 6, 15], &addmul));
+        assert!(calibration_can_be_valid(
+            7290,
+            &[6, 8, 6, 15],
+            &addmulconcat
+        ));
+        assert!(!calibration_can_be_valid(83, &[17, 5], &addmulconcat));
+    }
+}
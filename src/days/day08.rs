@@ -0,0 +1,105 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::FoldWhile::{Continue, Done};
+use itertools::Itertools;
+
+use crate::error::AocError;
+use crate::grid::{to_signed, Grid, IPoint};
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day08;
+
+// Find the antinodes of every pair of antennae with the same frequency, both without and with
+// resonant harmonics
+pub fn antinodes(city: &Grid<char>) -> (HashSet<IPoint>, HashSet<IPoint>) {
+    let mut antennae = HashMap::<char, HashSet<IPoint>>::new();
+    for (p, c) in city.iter().filter(|(_, c)| c.is_alphanumeric()) {
+        antennae.entry(*c).or_default().insert(to_signed(p));
+    }
+
+    let validate_antinode = |n: IPoint| city.in_bounds(n).then_some(n);
+
+    let mut antinodes = HashSet::<IPoint>::new();
+    let mut antinodes_harmonics = HashSet::<IPoint>::new();
+
+    let mut add_antinode = |n| antinodes.insert(n);
+    let mut add_resonant_antinodes = |start: IPoint, delta: IPoint| {
+        std::iter::repeat(delta).fold_while(start, |n, d| {
+            antinodes_harmonics.insert(n);
+            validate_antinode(n + d).map(Continue).unwrap_or(Done(n))
+        })
+    };
+    for (_, positions) in antennae.iter() {
+        for pair in positions.iter().combinations(2) {
+            let (a, b) = (*pair[0], *pair[1]);
+            let d = a - b;
+
+            validate_antinode(a + d).map(&mut add_antinode);
+            validate_antinode(b - d).map(&mut add_antinode);
+
+            let _ = &mut add_resonant_antinodes(a, d);
+            let _ = &mut add_resonant_antinodes(b, -d);
+        }
+    }
+
+    (antinodes, antinodes_harmonics)
+}
+
+impl Solution for Day08 {
+    type Input<'a> = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(city: &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(antinodes(city).0.len())
+    }
+
+    fn part2(city: &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(antinodes(city).1.len())
+    }
+
+    fn report1(n: &usize) -> String {
+        format!("Antinodes: {}", n)
+    }
+
+    fn report2(n: &usize) -> String {
+        format!("Antinodes (with harmonics): {}", n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day08::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day08::part1(&input).unwrap(), 14);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day08::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day08::part2(&input).unwrap(), 34);
+    }
+}
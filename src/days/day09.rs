@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+
+use itertools::repeat_n;
+use itertools::Itertools;
+
+use crate::error::AocError;
+use crate::frames::{Recorder, Rgb, BLACK};
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day09;
+
+pub type Disk = Vec<Option<u64>>;
+
+pub fn checksum<'a, T: Iterator<Item = &'a Option<u64>>>(disk: T) -> u64 {
+    disk.enumerate().fold(0, |acc, (pos, block)| match block {
+        Some(file_id) => acc + (pos as u64) * file_id,
+        None => acc,
+    })
+}
+
+pub fn defragment_blocks(blocks: &[Option<u64>]) -> Disk {
+    let mut defrag = blocks.to_vec();
+    let mut front = 0;
+    let mut back = blocks.len() - 1;
+    while back > front {
+        while defrag[front].is_some() {
+            front += 1
+        }
+        if defrag[back].is_some() {
+            defrag[front] = defrag[back];
+            defrag[back] = None;
+            front += 1
+        }
+        back -= 1;
+    }
+    defrag
+}
+
+pub fn defragment_files(blocks: &[Option<u64>]) -> Disk {
+    defragment_files_with(blocks, |_| {})
+}
+
+// Defragment by file, calling on_move with the disk after each file is moved
+fn defragment_files_with<F: FnMut(&[Option<u64>])>(blocks: &[Option<u64>], mut on_move: F) -> Disk {
+    let mut unoccupied = blocks
+        .iter()
+        .chunk_by(|&b| b.is_some())
+        .into_iter()
+        .scan(0, |pos, (occupied, run)| {
+            let n = run.count();
+            *pos += n;
+            if !occupied {
+                Some(Some((*pos - n, n)))
+            } else {
+                Some(None)
+            }
+        })
+        .flatten()
+        .collect_vec();
+
+    let mut defrag = blocks.to_vec();
+    let mut back = defrag.len() - 1;
+    let mut file_end = back;
+    let mut cur_file = defrag[back];
+    let mut moved = HashSet::<u64>::new();
+    while back > 0 {
+        back -= 1;
+        if defrag[back] == cur_file {
+            continue;
+        } else if let Some(f) = cur_file {
+            // try to find somewhere to put the current file
+            if !moved.contains(&f) {
+                let file_size = file_end - back;
+                if let Some((chunk_idx, (pos, len))) = unoccupied
+                    .iter()
+                    .find_position(|(pos, n)| *n >= file_size && *pos < back)
+                {
+                    for n in 0..file_size {
+                        defrag[pos + n] = defrag[file_end - n];
+                        defrag[file_end - n] = None;
+                    }
+                    unoccupied[chunk_idx] = (*pos + file_size, len - file_size);
+                    on_move(&defrag);
+                }
+                moved.insert(f);
+            }
+        }
+        cur_file = defrag[back];
+        file_end = back;
+    }
+
+    defrag
+}
+
+// The disk laid out in a square, one cell per block
+fn disk_grid(disk: &[Option<u64>]) -> Grid<Option<u64>> {
+    let width = (disk.len() as f64).sqrt().ceil().max(1.0) as usize;
+    let height = disk.len().div_ceil(width);
+    Grid::from_fn(width, height, |p| {
+        disk.get(p.y * width + p.x).copied().flatten()
+    })
+}
+
+// Free blocks are black, and each file gets a colour of its own
+fn color(block: &Option<u64>) -> Rgb {
+    match *block {
+        Some(id) => [
+            (55 + id * 67 % 200) as u8,
+            (55 + id * 131 % 200) as u8,
+            (55 + id * 29 % 200) as u8,
+        ],
+        None => BLACK,
+    }
+}
+
+impl Solution for Day09 {
+    type Input<'a> = Disk;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or(AocError::InvalidInput("empty input".to_string()))?;
+
+        let blocks = line
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .ok_or_else(|| AocError::InvalidInput(format!("'{c}' is not a digit")))
+            })
+            .try_collect::<_, Vec<_>, _>()?
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, n)| match idx % 2 {
+                0 => repeat_n(Some((idx as u64) / 2), *n as usize),
+                1 => repeat_n(None, *n as usize),
+                _ => unreachable!(),
+            })
+            .collect_vec();
+        Ok(blocks)
+    }
+
+    fn part1(blocks: &Self::Input<'_>) -> Result<u64, AocError> {
+        Ok(checksum(defragment_blocks(blocks).iter()))
+    }
+
+    fn part2(blocks: &Self::Input<'_>) -> Result<u64, AocError> {
+        Ok(checksum(defragment_files(blocks).iter()))
+    }
+
+    // Files being moved to the front of the disk, as in part 2
+    fn animate(blocks: &Self::Input<'_>, frames: &mut Recorder) -> Result<(), AocError> {
+        let mut recorded = frames.record(&disk_grid(blocks), color);
+        defragment_files_with(blocks, |disk| {
+            if recorded.is_ok() {
+                recorded = frames.record(&disk_grid(disk), color);
+            }
+        });
+        recorded
+    }
+
+    fn report1(checksum: &u64) -> String {
+        format!("Checksum after defragmenting by block: {:?}", checksum)
+    }
+
+    fn report2(checksum: &u64) -> String {
+        format!("Checksum after defragmenting by file:  {}", checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2333133121414131402
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day09::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day09::part1(&input).unwrap(), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day09::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day09::part2(&input).unwrap(), 2858);
+    }
+
+    #[test]
+    fn moves_blocks_from_the_end() {
+        // 12345 is the disk 0..111....22222
+        let disk = Day09::parse_example("12345").unwrap();
+        let expected = [0, 2, 2, 1, 1, 1, 2, 2, 2].map(Some);
+        assert_eq!(defragment_blocks(&disk)[..9], expected);
+        assert!(defragment_blocks(&disk)[9..].iter().all(Option::is_none));
+    }
+}
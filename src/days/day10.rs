@@ -0,0 +1,94 @@
+use crate::bfs::bfs_all;
+use crate::error::AocError;
+use crate::grid::{Grid, UPoint};
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day10;
+
+fn uphill(map: &Grid<u8>, p: UPoint) -> Vec<UPoint> {
+    map.neighbors(p)
+        .into_iter()
+        .filter(|&d| map[d] == map[p] + 1)
+        .collect()
+}
+
+// Number of peaks reachable from the trailhead
+pub fn score(map: &Grid<u8>, trailhead: UPoint) -> usize {
+    bfs_all(&[trailhead], |p| uphill(map, *p))
+        .dists()
+        .filter(|(p, _)| map[**p] == 9)
+        .count()
+}
+
+// Number of distinct hiking trails from p to a peak
+pub fn rating(map: &Grid<u8>, p: UPoint) -> usize {
+    if map[p] == 9 {
+        1
+    } else {
+        uphill(map, p).into_iter().map(|d| rating(map, d)).sum()
+    }
+}
+
+impl Solution for Day10 {
+    type Input<'a> = (Grid<u8>, Vec<UPoint>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let map = Grid::parse(input, |c| c.to_digit(10).map(|d| d as u8))?;
+        let trailheads: Vec<UPoint> = map.positions(|&h| h == 0).collect();
+        Ok((map, trailheads))
+    }
+
+    fn part1((map, trailheads): &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(trailheads.iter().map(|h| score(map, *h)).sum())
+    }
+
+    fn part2((map, trailheads): &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(trailheads.iter().map(|h| rating(map, *h)).sum())
+    }
+
+    fn report1(score: &usize) -> String {
+        format!("Total score of hiking map: {}", score)
+    }
+
+    fn report2(rating: &usize) -> String {
+        format!("Total rating of hiking map: {}", rating)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day10::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&input).unwrap(), 36);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day10::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day10::part2(&input).unwrap(), 81);
+    }
+
+    #[test]
+    fn single_trailhead() {
+        let (map, trailheads) = Day10::parse_example("0123\n1234\n8765\n9876\n").unwrap();
+        assert_eq!(score(&map, trailheads[0]), 1);
+        assert_eq!(rating(&map, trailheads[0]), 16);
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::error::AocError;
+use crate::memo::Memo;
+use crate::params::{Param, Params};
+use crate::solution::{Answer, Solution};
+
+pub struct Day11;
+
+pub type PebbleValue = u64;
+fn split_pebble(p: PebbleValue) -> (PebbleValue, PebbleValue) {
+    let n = 10u64.pow(p.ilog10().div_ceil(2));
+    (p / n, p % n)
+}
+
+// The number of pebbles a pebble with value v turns into after some blinks
+pub fn n_descendants(
+    v: PebbleValue,
+    blinks: usize,
+    memo: &mut Memo<(PebbleValue, usize), usize>,
+) -> usize {
+    if blinks == 0 {
+        return 1;
+    }
+    memo.get((v, blinks), |memo| {
+        let mut next = |x| n_descendants(x, blinks - 1, memo);
+        if v == 0 {
+            next(1)
+        } else if v.ilog10() % 2 == 1 {
+            let (left, right) = split_pebble(v);
+            next(left) + next(right)
+        } else {
+            next(2024 * v)
+        }
+    })
+}
+
+pub fn blink(ps: &[PebbleValue], blinks: usize) -> usize {
+    let mut memo = Memo::new();
+    ps.iter()
+        .map(|p| n_descendants(*p, blinks, &mut memo))
+        .sum()
+}
+
+// The number of pebbles after some blinks
+pub struct Pebbles {
+    blinks: usize,
+    count: usize,
+}
+
+impl fmt::Display for Pebbles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)
+    }
+}
+
+impl Answer for Pebbles {
+    fn to_json(&self) -> Value {
+        self.count.to_json()
+    }
+}
+
+impl Solution for Day11 {
+    // The pebbles and how many times to blink in each part
+    type Input<'a> = (Vec<PebbleValue>, usize, usize);
+    type Answer1 = Pebbles;
+    type Answer2 = Pebbles;
+
+    const PARAMS: &'static [Param] = &[Param::new("blinks1", "25"), Param::new("blinks2", "75")];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or(AocError::InvalidInput("empty input".to_string()))?;
+
+        let ps: Vec<PebbleValue> = line
+            .split_whitespace()
+            .map(&str::parse::<PebbleValue>)
+            .collect::<Result<Vec<PebbleValue>, _>>()?;
+        Ok((ps, params.get("blinks1")?, params.get("blinks2")?))
+    }
+
+    fn part1((ps, blinks, _): &Self::Input<'_>) -> Result<Pebbles, AocError> {
+        Ok(Pebbles {
+            blinks: *blinks,
+            count: blink(ps, *blinks),
+        })
+    }
+
+    fn part2((ps, _, blinks): &Self::Input<'_>) -> Result<Pebbles, AocError> {
+        Ok(Pebbles {
+            blinks: *blinks,
+            count: blink(ps, *blinks),
+        })
+    }
+
+    fn report1(Pebbles { blinks, count }: &Pebbles) -> String {
+        format!("After {blinks} blinks: {count}")
+    }
+
+    fn report2(Pebbles { blinks, count }: &Pebbles) -> String {
+        format!("After {blinks} blinks: {count}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+125 17
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day11::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&input).unwrap().count, 55312);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day11::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day11::part2(&input).unwrap().count, 65601038650482);
+    }
+
+    #[test]
+    fn counts_descendants() {
+        assert_eq!(blink(&[0, 1, 10, 99, 999], 1), 7);
+        assert_eq!(blink(&[125, 17], 6), 22);
+        let mut memo = Memo::new();
+        // 17 -> 1 7 -> 2024 14168 -> 20 24 28676032
+        assert_eq!(n_descendants(17, 3, &mut memo), 3);
+    }
+}
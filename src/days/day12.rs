@@ -0,0 +1,131 @@
+use itertools::Itertools;
+
+use crate::bfs::label_components;
+use crate::error::AocError;
+use crate::grid::{to_signed, Grid, SparseGrid};
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day12;
+
+// Every plot has four sides, and each neighbour in the same region covers one of them
+pub fn perimiter(region: &SparseGrid<char>) -> usize {
+    region.points().map(|p| 4 - region.neighbors(p).len()).sum()
+}
+
+pub fn n_sides(region: &SparseGrid<char>) -> usize {
+    let Some((north_west, south_east)) = region.bounds() else {
+        return 0;
+    };
+    let (north, south) = (north_west.y, south_east.y);
+    let (west, east) = (north_west.x, south_east.x);
+    let hor_sides: usize = (north - 1..=south)
+        .map(|y| {
+            (west..=east)
+                .chunk_by(|&x| {
+                    match (
+                        region.contains((x, y + 1).into()),
+                        region.contains((x, y).into()),
+                    ) {
+                        (false, true) => -1isize,
+                        (true, false) => 1,
+                        _ => 0,
+                    }
+                })
+                .into_iter()
+                .map(|(s, _)| s.unsigned_abs())
+                .sum::<usize>()
+        })
+        .sum();
+    let ver_sides: usize = (west - 1..=east)
+        .map(|x| {
+            (north..=south)
+                .chunk_by(|&y| {
+                    match (
+                        region.contains((x + 1, y).into()),
+                        region.contains((x, y).into()),
+                    ) {
+                        (false, true) => -1isize,
+                        (true, false) => 1,
+                        _ => 0,
+                    }
+                })
+                .into_iter()
+                .map(|(s, _)| s.unsigned_abs())
+                .sum::<usize>()
+        })
+        .sum();
+    hor_sides + ver_sides
+}
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<SparseGrid<char>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let garden: Grid<char> = input.parse()?;
+
+        let (labels, n_regions) =
+            label_components(&garden, |a, b| a == b, |p| garden.neighbors(*p));
+        let mut components = vec![SparseGrid::new(); n_regions];
+        for (p, &label) in labels.iter() {
+            components[label].insert(to_signed(p), garden[p]);
+        }
+
+        Ok(components)
+    }
+
+    fn part1(components: &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(components.iter().map(|c| c.len() * perimiter(c)).sum())
+    }
+
+    fn part2(components: &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(components.iter().map(|c| c.len() * n_sides(c)).sum())
+    }
+
+    fn report1(price: &usize) -> String {
+        format!("Total price of fences: {price}")
+    }
+
+    fn report2(price_sides: &usize) -> String {
+        format!("Total price of fences (discounted): {price_sides}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day12::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&input).unwrap(), 1930);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day12::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day12::part2(&input).unwrap(), 1206);
+    }
+
+    #[test]
+    fn small_garden() {
+        let input = Day12::parse_example("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        assert_eq!(Day12::part1(&input).unwrap(), 140);
+        assert_eq!(Day12::part2(&input).unwrap(), 80);
+    }
+}
@@ -0,0 +1,208 @@
+use ::std::cmp::Ordering::*;
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    combinator::map,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+use std::collections::BTreeMap;
+
+use crate::cycle::brent;
+use crate::error::AocError;
+use crate::frames::{Recorder, BLACK, GREEN};
+use crate::grid::{Grid, IPoint, SparseGrid};
+use crate::params::{Param, Params};
+use crate::parse::{lines, parse_all, point};
+use crate::render::{Canvas, Color};
+use crate::solution::Solution;
+
+pub struct Day14;
+
+type Room = (isize, isize);
+
+#[derive(Debug)]
+pub struct Robot {
+    p: IPoint,
+    v: IPoint,
+}
+
+fn robot(input: &str) -> IResult<&str, Robot> {
+    map(
+        separated_pair(
+            preceded(tag("p="), point),
+            space1,
+            preceded(tag("v="), point),
+        ),
+        |(p, v)| Robot { p, v },
+    )(input)
+}
+
+pub fn move_robot(r: &Robot, t: isize, (w, h): (isize, isize)) -> IPoint {
+    IPoint {
+        x: (r.p.x + r.v.x * t).rem_euclid(w),
+        y: (r.p.y + r.v.y * t).rem_euclid(h),
+    }
+}
+
+// The product of the number of robots in each quadrant of the room after t seconds
+pub fn safety_factor(robots: &[Robot], t: isize, (w, h): (isize, isize)) -> usize {
+    let final_pos = robots
+        .iter()
+        .map(|r| move_robot(r, t, (w, h)))
+        .collect_vec();
+    let mut quads = BTreeMap::<_, usize>::new();
+    for p in final_pos.iter() {
+        *quads
+            .entry((p.x.cmp(&(w / 2)), p.y.cmp(&(h / 2))))
+            .or_default() += 1
+    }
+
+    quads
+        .iter()
+        .filter_map(|((h, v), n)| match (h, v) {
+            (_, Equal) | (Equal, _) => None,
+            _ => Some(n),
+        })
+        .product()
+}
+
+// The tiles with robots on them at time t
+fn room(robots: &[Robot], t: isize, size: Room) -> SparseGrid<char> {
+    robots
+        .iter()
+        .map(|r| (move_robot(r, t, size), '*'))
+        .collect()
+}
+
+// How many seconds it takes for every robot to be back where it started. The robots wrap around
+// the room, so their positions repeat from the very start.
+fn period(robots: &[Robot], size: Room) -> isize {
+    let start: Vec<IPoint> = robots.iter().map(|r| r.p).collect();
+    let cycle = brent(&start, |positions| {
+        positions
+            .iter()
+            .zip(robots)
+            .map(|(&p, r)| move_robot(&Robot { p, v: r.v }, 1, size))
+            .collect()
+    });
+    cycle.length as isize
+}
+
+// The first time no two robots are on the same tile
+fn first_arrangement(robots: &[Robot], size: Room) -> Option<isize> {
+    (0..period(robots, size)).find(|&t| room(robots, t, size).len() == robots.len())
+}
+
+impl Solution for Day14 {
+    type Input<'a> = (Vec<Robot>, Room);
+    type Answer1 = usize;
+    type Answer2 = isize;
+
+    // The size of the room
+    const PARAMS: &'static [Param] = &[
+        Param::profiles("w", "11", "101"),
+        Param::profiles("h", "7", "103"),
+    ];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        let robots = parse_all(lines(robot), input)?;
+        // Robots wrap around the room, which needs at least one tile each way
+        let side = |name: &str| match params.get::<isize>(name)? {
+            n if n < 1 => Err(AocError::InvalidParam(format!("{name} must be at least 1"))),
+            n => Ok(n),
+        };
+        Ok((robots, (side("w")?, side("h")?)))
+    }
+
+    fn part1((robots, size): &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(safety_factor(robots, 100, *size))
+    }
+
+    // The first arrangement where no two robots overlap, which is when they draw the tree
+    fn part2((robots, size): &Self::Input<'_>) -> Result<isize, AocError> {
+        first_arrangement(robots, *size).ok_or_else(|| {
+            AocError::NoSolution("no arrangement without overlapping robots".to_string())
+        })
+    }
+
+    // The room at the first arrangement without overlapping robots
+    fn draw(input: &Self::Input<'_>) -> Result<Option<Canvas>, AocError> {
+        let t = Self::part2(input)?;
+        let (robots, size) = input;
+        let floor = Grid::new(size.0 as usize, size.1 as usize, '.');
+        let mut canvas = Canvas::from(&floor);
+        canvas.mark(room(robots, t, *size).points(), Some('*'), Color::Green);
+        Ok(Some(canvas))
+    }
+
+    // The robots moving about until they first stop overlapping
+    fn animate((robots, size): &Self::Input<'_>, frames: &mut Recorder) -> Result<(), AocError> {
+        for t in 0..period(robots, *size) {
+            let room = room(robots, t, *size);
+            let mut floor = Grid::new(size.0 as usize, size.1 as usize, false);
+            for p in room.points() {
+                floor[p] = true;
+            }
+            frames.record(&floor, |&robot| if robot { GREEN } else { BLACK })?;
+            if room.len() == robots.len() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn report1(answer: &usize) -> String {
+        format!("Safety factor: {:?}", answer)
+    }
+
+    fn report2(t: &isize) -> String {
+        format!("First arrangement without overlapping robots: t = {t}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+    const EXAMPLE_ROOM: (isize, isize) = (11, 7);
+
+    #[test]
+    fn part1_example() {
+        let input = Day14::parse_example(EXAMPLE).unwrap();
+        assert_eq!(input.1, EXAMPLE_ROOM);
+        assert_eq!(Day14::part1(&input).unwrap(), 12);
+    }
+
+    #[test]
+    fn robots_wrap_around() {
+        let (_, robots) = robot("p=2,4 v=2,-3").unwrap();
+        let expected = [(2, 4), (4, 1), (6, 5), (8, 2), (10, 6), (1, 3)];
+        for (t, p) in expected.into_iter().enumerate() {
+            assert_eq!(move_robot(&robots, t as isize, EXAMPLE_ROOM), p.into());
+        }
+    }
+
+    #[test]
+    fn robots_repeat() {
+        // The sides are prime, so every robot that moves both ways takes w * h seconds
+        let (robots, _) = Day14::parse_example(EXAMPLE).unwrap();
+        assert_eq!(period(&robots, EXAMPLE_ROOM), 11 * 7);
+        assert_eq!(period(&robots, (101, 103)), 101 * 103);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::AocError;
+use crate::frames::{Recorder, Rgb, BLACK, BROWN, GRAY, RED};
+use crate::grid::{to_signed, CardinalDirection, Grid, IPoint};
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct Day15;
+
+type Point = IPoint;
+
+fn attempt_move(warehouse: &mut Grid<char>, pos: Point, dir: Point) -> Point {
+    let to = pos + dir;
+    let mut p = to;
+    while warehouse[p] == 'O' {
+        p += dir;
+    }
+    if warehouse[p] == '#' {
+        pos
+    } else {
+        warehouse[p] = 'O';
+        warehouse[to] = '.';
+        to
+    }
+}
+
+#[allow(dead_code)]
+fn attempt_move_wide(warehouse: &mut Grid<char>, pos: Point, dir: Point) -> Point {
+    let to = pos + dir;
+    let mut p = to;
+    while warehouse[p] == '[' || warehouse[p] == ']' {
+        p += dir;
+    }
+    if warehouse[p] == '#' {
+        pos
+    } else {
+        warehouse[p] = 'O';
+        warehouse[to] = '.';
+        to
+    }
+}
+
+fn gps(warehouse: &Grid<char>) -> usize {
+    warehouse
+        .positions(|&c| c == 'O')
+        .map(|p| 100 * p.y + p.x)
+        .sum()
+}
+
+// Follow the instructions, calling on_move with the warehouse and where the robot is before the
+// first move and after each one
+fn simulate<F>(
+    warehouse: &Grid<char>,
+    mut pos: Point,
+    instructions: &[CardinalDirection],
+    mut on_move: F,
+) -> Result<Grid<char>, AocError>
+where
+    F: FnMut(&Grid<char>, Point) -> Result<(), AocError>,
+{
+    let mut warehouse = warehouse.clone();
+    on_move(&warehouse, pos)?;
+    for i in instructions {
+        pos = attempt_move(&mut warehouse, pos, i.delta());
+        on_move(&warehouse, pos)?;
+    }
+    Ok(warehouse)
+}
+
+fn color(c: &char) -> Rgb {
+    match c {
+        '#' => GRAY,
+        'O' | '[' | ']' => BROWN,
+        '@' => RED,
+        _ => BLACK,
+    }
+}
+
+impl Solution for Day15 {
+    type Input<'a> = (Grid<char>, Point, Vec<CardinalDirection>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let mut warehouse: Grid<char> = input.parse()?;
+        let pos = to_signed(
+            warehouse
+                .find(&'@')
+                .ok_or(AocError::MissingMarker("robot"))?,
+        );
+        warehouse[pos] = '.';
+
+        let instructions = input
+            .split_once("\n\n")
+            .map(|(_, i)| i)
+            .ok_or(AocError::MissingMarker("instructions"))?
+            .lines()
+            .flat_map(str::chars)
+            .map(CardinalDirection::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok((warehouse, pos, instructions))
+    }
+
+    fn part1((warehouse, pos, instructions): &Self::Input<'_>) -> Result<usize, AocError> {
+        let warehouse = simulate(warehouse, *pos, instructions, |_, _| Ok(()))?;
+        Ok(gps(&warehouse))
+    }
+
+    fn part2(_: &Self::Input<'_>) -> Result<usize, AocError> {
+        Err(AocError::Unsolved)
+    }
+
+    // The robot pushing boxes around
+    fn animate(
+        (warehouse, pos, instructions): &Self::Input<'_>,
+        frames: &mut Recorder,
+    ) -> Result<(), AocError> {
+        simulate(warehouse, *pos, instructions, |warehouse, robot| {
+            let mut warehouse = warehouse.clone();
+            warehouse[robot] = '@';
+            frames.record(&warehouse, color)
+        })?;
+        Ok(())
+    }
+
+    fn report1(score: &usize) -> String {
+        format!("GPS score: {}", score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let input = Day15::parse_example(
+            "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+",
+        )
+        .unwrap();
+        assert_eq!(Day15::part1(&input).unwrap(), 10092);
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::dijkstra::{dijkstra_by, dijkstra_dag_by, k_shortest_paths_by};
+use crate::error::AocError;
+use crate::grid::{step, CardinalDirection, Grid, UPoint};
+use crate::params::Params;
+use crate::render::{Canvas, Color};
+use crate::solution::Solution;
+use std::collections::BTreeSet;
+
+pub struct Day16;
+
+fn is_accessible(maze: &Grid<char>, p: UPoint) -> bool {
+    maze.get(p).is_some_and(|&c| c != '#')
+}
+
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub struct Reindeer {
+    pos: UPoint,
+    orientation: CardinalDirection,
+}
+
+impl std::fmt::Debug for Reindeer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.pos.x, self.pos.y, self.orientation)
+    }
+}
+
+fn neighbors(r: &Reindeer, maze: &Grid<char>) -> Vec<(Reindeer, usize)> {
+    let mut out = vec![
+        (
+            Reindeer {
+                pos: r.pos,
+                orientation: r.orientation.clockwise(),
+            },
+            1000,
+        ),
+        (
+            Reindeer {
+                pos: r.pos,
+                orientation: r.orientation.counterclockwise(),
+            },
+            1000,
+        ),
+    ];
+    if let Some(pos) = step(r.pos, r.orientation).filter(|&pos| is_accessible(maze, pos)) {
+        out.push((
+            Reindeer {
+                pos,
+                orientation: r.orientation,
+            },
+            1,
+        ))
+    }
+    out
+}
+
+fn shortest(maze: &Grid<char>, start: &Reindeer, end: UPoint) -> Result<usize, AocError> {
+    let dists = dijkstra_by(start, |r: &Reindeer| r.pos == end, |r| neighbors(r, maze));
+    let shortest = dists
+        .end()
+        .and_then(|r| dists.dist(&r))
+        .ok_or(AocError::Unreachable("the finish"))?;
+    Ok(shortest)
+}
+
+// How much worse than the best route each of the next n best routes is. Routes tied with the best
+// one count as runners-up too.
+pub fn runners_up((maze, start, end): &(Grid<char>, Reindeer, UPoint), n: usize) -> Vec<usize> {
+    let mut routes =
+        k_shortest_paths_by(start, |r: &Reindeer| r.pos == *end, |r| neighbors(r, maze))
+            .take(n + 1)
+            .map(|path| path.dist());
+    let Some(best) = routes.next() else {
+        return Vec::new();
+    };
+    routes.map(|dist| dist - best).collect()
+}
+
+impl Solution for Day16 {
+    type Input<'a> = (Grid<char>, Reindeer, UPoint);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        let maze: Grid<char> = input.parse()?;
+        let start = maze
+            .find(&'S')
+            .ok_or(AocError::MissingMarker("start position"))?;
+        let end = maze
+            .find(&'E')
+            .ok_or(AocError::MissingMarker("end position"))?;
+
+        let start = Reindeer {
+            pos: start,
+            orientation: CardinalDirection::East,
+        };
+        Ok((maze, start, end))
+    }
+
+    fn part1((maze, start, end): &Self::Input<'_>) -> Result<usize, AocError> {
+        shortest(maze, start, *end)
+    }
+
+    fn part2((maze, start, end): &Self::Input<'_>) -> Result<usize, AocError> {
+        let dag = dijkstra_dag_by(start, |r: &Reindeer| r.pos == *end, |r| neighbors(r, maze));
+        let on_shortest_path: BTreeSet<UPoint> =
+            dag.nodes_on_optimal_paths().iter().map(|r| r.pos).collect();
+        Ok(on_shortest_path.len())
+    }
+
+    // The maze with one of the best routes through it
+    fn draw((maze, start, end): &Self::Input<'_>) -> Result<Option<Canvas>, AocError> {
+        let dists = dijkstra_by(start, |r: &Reindeer| r.pos == *end, |r| neighbors(r, maze));
+        let route = dists
+            .end()
+            .and_then(|r| dists.get(&r))
+            .ok_or(AocError::Unreachable("the finish"))?;
+        let mut canvas = Canvas::from(maze);
+        canvas
+            .mark(maze.positions(|&c| c == '#'), None, Color::Gray)
+            .path(&route, |r| r.pos, Color::Green)
+            .mark([start.pos], Some('S'), Color::Red)
+            .mark([*end], Some('E'), Color::Red);
+        Ok(Some(canvas))
+    }
+
+    fn report1(shortest: &usize) -> String {
+        format!("Best possible score: {}", shortest)
+    }
+
+    fn report2(n_tiles: &usize) -> String {
+        format!("Number of tiles on any shortest path: {}", n_tiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    const SECOND_EXAMPLE: &str = "\
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day16::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day16::part1(&input).unwrap(), 7036);
+        let input = Day16::parse_example(SECOND_EXAMPLE).unwrap();
+        assert_eq!(Day16::part1(&input).unwrap(), 11048);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day16::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day16::part2(&input).unwrap(), 45);
+        let input = Day16::parse_example(SECOND_EXAMPLE).unwrap();
+        assert_eq!(Day16::part2(&input).unwrap(), 64);
+    }
+
+    #[test]
+    fn runners_up_example() {
+        // How much worse each of the next best routes is than the best one, in cost order
+        let input = Day16::parse_example(EXAMPLE).unwrap();
+        assert_eq!(runners_up(&input, 4), vec![0, 0, 2000, 2000]);
+        let input = Day16::parse_example(SECOND_EXAMPLE).unwrap();
+        assert_eq!(runners_up(&input, 4), vec![0, 8, 8, 1000]);
+    }
+
+    #[test]
+    fn missing_markers() {
+        let no_end = Day16::parse_example("#####\n#S..#\n#####\n");
+        assert!(matches!(
+            no_end,
+            Err(AocError::MissingMarker("end position"))
+        ));
+        let input = Day16::parse_example("#####\n#S#E#\n#####\n").unwrap();
+        assert!(matches!(
+            Day16::part1(&input),
+            Err(AocError::Unreachable(_))
+        ));
+    }
+}
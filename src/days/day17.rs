@@ -0,0 +1,309 @@
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, oct_digit1, u64},
+    combinator::{map, map_res},
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use crate::error::AocError;
+use crate::params::Params;
+use crate::parse::{blank_line, header, integer, integers, parse_all};
+use crate::solution::Solution;
+
+pub struct Day17;
+
+#[derive(Clone)]
+pub struct CSC {
+    a: u64,
+    b: u64,
+    c: u64,
+    tape: Vec<u8>,
+    iptr: usize,
+    output: Vec<u8>,
+}
+
+// Whether to advance the instruction pointer past the instruction just executed
+type CSCResult = Result<bool, AocError>;
+
+impl CSC {
+    fn invalid(&self, what: &str) -> AocError {
+        AocError::InvalidState(format!("{what} at instruction {}", self.iptr))
+    }
+
+    fn literal(&self) -> Result<u64, AocError> {
+        self.tape
+            .get(self.iptr + 1)
+            .map(|c| *c as u64)
+            .ok_or_else(|| self.invalid("missing operand"))
+    }
+
+    fn combo(&self) -> Result<u64, AocError> {
+        match self.literal()? {
+            op @ 0..=3 => Ok(op),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            op => Err(self.invalid(&format!("invalid combo operand {op}"))),
+        }
+    }
+
+    // A divided by 2 to the power of the combo operand, as a right shift
+    fn divide(&self) -> Result<u64, AocError> {
+        let v = self.combo()?;
+        u32::try_from(v)
+            .ok()
+            .and_then(|v| self.a.checked_shr(v))
+            .ok_or_else(|| self.invalid(&format!("shift of A by {v} bits")))
+    }
+
+    fn adv(&mut self) -> CSCResult {
+        self.a = self.divide()?;
+        Ok(true)
+    }
+
+    fn bdv(&mut self) -> CSCResult {
+        self.b = self.divide()?;
+        Ok(true)
+    }
+
+    fn cdv(&mut self) -> CSCResult {
+        self.c = self.divide()?;
+        Ok(true)
+    }
+
+    fn bxl(&mut self) -> CSCResult {
+        self.b ^= self.literal()?;
+        Ok(true)
+    }
+
+    fn bst(&mut self) -> CSCResult {
+        self.b = self.combo()? % 8;
+        Ok(true)
+    }
+
+    fn jnz(&mut self) -> CSCResult {
+        if self.a != 0 {
+            self.iptr = self.literal()? as usize;
+            Ok(false)
+        } else {
+            Ok(true)
+        }
+    }
+
+    fn bxc(&mut self) -> CSCResult {
+        self.b ^= self.c;
+        Ok(true)
+    }
+
+    fn out(&mut self) -> CSCResult {
+        self.output.push((self.combo()? % 8) as u8);
+        Ok(true)
+    }
+
+    // Run until the instruction pointer moves past the end of the program
+    pub fn run(&mut self) -> Result<(), AocError> {
+        while let Some(&opcode) = self.tape.get(self.iptr) {
+            let advance = match opcode {
+                0 => self.adv(),
+                1 => self.bxl(),
+                2 => self.bst(),
+                3 => self.jnz(),
+                4 => self.bxc(),
+                5 => self.out(),
+                6 => self.bdv(),
+                7 => self.cdv(),
+                _ => Err(self.invalid(&format!("invalid opcode {opcode}"))),
+            }?;
+            if advance {
+                self.iptr += 2;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_reg(input: &str) -> IResult<&str, u64> {
+    alt((
+        (map_res(preceded(char('0'), oct_digit1), |s| {
+            u64::from_str_radix(s, 8)
+        })),
+        u64,
+    ))(input)
+}
+
+fn parse_csc(input: &str) -> IResult<&str, CSC> {
+    map(
+        separated_pair(
+            tuple((
+                terminated(header("Register A", parse_reg), line_ending),
+                terminated(header("Register B", integer), line_ending),
+                header("Register C", integer),
+            )),
+            blank_line,
+            header("Program", integers(",")),
+        ),
+        |((a, b, c), tape)| CSC {
+            a,
+            b,
+            c,
+            tape,
+            iptr: 0,
+            output: Vec::new(),
+        },
+    )(input)
+}
+
+// The smallest value of register A for which the program outputs itself, assuming, as the puzzle
+// programs do, that each pass through the loop outputs one value and shifts A right by three bits.
+// A is then built up three bits at a time, keeping every candidate that reproduces the last n
+// values of the program.
+pub fn find_quine(csc: &CSC) -> Option<u64> {
+    let l = csc.tape.len();
+    let mut candidates = vec![0];
+    for n in 1..=l {
+        candidates = candidates
+            .iter()
+            .flat_map(|a| (0..8).map(move |k| a * 8 + k))
+            .filter(|&a| {
+                let mut comp = csc.clone();
+                comp.a = a;
+                comp.run().is_ok() && comp.output == comp.tape[l - n..]
+            })
+            .collect();
+    }
+    candidates.into_iter().min()
+}
+
+impl Solution for Day17 {
+    type Input<'a> = CSC;
+    type Answer1 = String;
+    type Answer2 = u64;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(parse_all(parse_csc, input)?)
+    }
+
+    fn part1(comp: &Self::Input<'_>) -> Result<String, AocError> {
+        let mut c = comp.clone();
+        c.run()?;
+        Ok(c.output.iter().map(|&c| c.to_string()).join(","))
+    }
+
+    fn part2(comp: &Self::Input<'_>) -> Result<u64, AocError> {
+        find_quine(comp).ok_or_else(|| {
+            AocError::NoSolution("no value of A makes the program output itself".to_string())
+        })
+    }
+
+    fn report1(output: &String) -> String {
+        format!("The output of the chronospatial computer is: {output}")
+    }
+
+    fn report2(v: &u64) -> String {
+        format!("Quine input: 0{:o} = {}", v, v)
+    }
+
+    // Each output digit is one octal digit of register A, so its octal form shows the program's
+    // structure
+    fn extras2(v: &u64) -> Option<Value> {
+        Some(json!({ "register_a_octal": format!("{v:o}") }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(a: u64, b: u64, c: u64, tape: Vec<u8>) -> CSC {
+        let mut comp = CSC {
+            a,
+            b,
+            c,
+            tape,
+            iptr: 0,
+            output: Vec::new(),
+        };
+        comp.run().unwrap();
+        comp
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Day17::parse_example(
+            "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+",
+        )
+        .unwrap();
+        assert_eq!(Day17::part1(&input).unwrap(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day17::parse_example(
+            "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+",
+        )
+        .unwrap();
+        assert_eq!(Day17::part2(&input).unwrap(), 117440);
+    }
+
+    #[test]
+    fn program_that_cannot_quine() {
+        // This program only shifts A right by one bit per output, so no value of A reproduces it and
+        // the search must end with an error
+        let input = Day17::parse_example(
+            "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+",
+        )
+        .unwrap();
+        assert!(Day17::part2(&input).is_err());
+    }
+
+    #[test]
+    fn instructions() {
+        assert_eq!(run(0, 0, 9, vec![2, 6]).b, 1);
+        assert_eq!(run(10, 0, 0, vec![5, 0, 5, 1, 5, 4]).output, vec![0, 1, 2]);
+        let comp = run(2024, 0, 0, vec![0, 1, 5, 4, 3, 0]);
+        assert_eq!(comp.output, vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(comp.a, 0);
+        assert_eq!(run(0, 29, 0, vec![1, 7]).b, 26);
+        assert_eq!(run(0, 2024, 43690, vec![4, 0]).b, 44354);
+    }
+
+    #[test]
+    fn invalid_states() {
+        let mut comp =
+            Day17::parse_example("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,7\n")
+                .unwrap();
+        assert!(matches!(comp.run(), Err(AocError::InvalidState(_))));
+        let mut comp =
+            Day17::parse_example("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1\n")
+                .unwrap();
+        assert!(matches!(comp.run(), Err(AocError::InvalidState(_))));
+        // B is too big to shift A by
+        let mut comp = Day17::parse_example(
+            "Register A: 1\nRegister B: 100\nRegister C: 0\n\nProgram: 6,5,5,5\n",
+        )
+        .unwrap();
+        assert!(matches!(comp.run(), Err(AocError::InvalidState(_))));
+    }
+}
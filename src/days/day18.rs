@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::bfs::bfs;
+use crate::dijkstra::{astar, manhattan, DijkstraPath};
+use crate::error::AocError;
+use crate::grid::{Grid, UPoint};
+use crate::params::{Param, Params};
+use crate::parse::{lines, parse_all, point};
+use crate::render::{Canvas, Color};
+use crate::solution::{Answer, Solution};
+
+pub struct Day18;
+
+// The memory space after the first n bytes have fallen, true marking corrupted cells
+fn corrupted(bytes: &[UPoint], n: usize, (w, h): (usize, usize)) -> Grid<bool> {
+    let mut memory = Grid::new(w + 1, h + 1, false);
+    for &b in bytes.iter().take(n) {
+        if let Some(c) = memory.get_mut(b) {
+            *c = true;
+        }
+    }
+    memory
+}
+
+fn accessible(pos: &UPoint, memory: &Grid<bool>) -> Vec<(UPoint, usize)> {
+    memory
+        .neighbors(*pos)
+        .into_iter()
+        .filter(|n| !memory[*n])
+        .map(|p| (p, 1))
+        .collect()
+}
+
+// A shortest path from the top left to the bottom right corner of memory
+fn route(memory: &Grid<bool>) -> Option<DijkstraPath<UPoint>> {
+    let start: UPoint = (0, 0).into();
+    let end: UPoint = (memory.width() - 1, memory.height() - 1).into();
+    astar(
+        &start,
+        |p| *p == end,
+        |p| accessible(p, memory),
+        manhattan(end),
+    )
+    .get(&end)
+}
+
+// Length of the shortest path from the top left to the bottom right corner of a memory space
+// extending to (w, h), after the first n bytes have fallen
+pub fn shortest_path(bytes: &[UPoint], n: usize, (w, h): (usize, usize)) -> Option<usize> {
+    route(&corrupted(bytes, n, (w, h))).map(|path| path.dist())
+}
+
+// The first byte after which the bottom right corner can no longer be reached
+pub fn first_blocking_byte(bytes: &[UPoint], (w, h): (usize, usize)) -> Option<UPoint> {
+    let start: UPoint = (0, 0).into();
+    let end: UPoint = (w, h).into();
+
+    let ns = Vec::from_iter(0..=bytes.len());
+    let blocked_after = ns.as_slice().partition_point(|n| {
+        let memory = corrupted(bytes, *n, (w, h));
+        bfs(&start, &end, |p| accessible(p, &memory)).contains_key(&end)
+    });
+    bytes.get(blocked_after.checked_sub(1)?).copied()
+}
+
+// A byte's position, written x,y as in the puzzle input
+#[derive(Debug, PartialEq, Eq)]
+pub struct Byte(pub UPoint);
+
+impl fmt::Display for Byte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.0.x, self.0.y)
+    }
+}
+
+impl Answer for Byte {}
+
+impl Solution for Day18 {
+    // The falling bytes, the bottom right corner of memory, and how many bytes fall in part 1
+    type Input<'a> = (Vec<UPoint>, (usize, usize), usize);
+    type Answer1 = usize;
+    type Answer2 = Byte;
+
+    const PARAMS: &'static [Param] = &[
+        Param::profiles("w", "7", "71"),
+        Param::profiles("h", "7", "71"),
+        Param::profiles("bytes", "12", "1024"),
+    ];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        let bytes = parse_all(lines(point), input)?;
+        let corner = |side: &str| {
+            params
+                .get::<usize>(side)?
+                .checked_sub(1)
+                .ok_or_else(|| AocError::InvalidParam(format!("{side} must be at least 1")))
+        };
+        Ok((bytes, (corner("w")?, corner("h")?), params.get("bytes")?))
+    }
+
+    fn part1((bytes, corner, n): &Self::Input<'_>) -> Result<usize, AocError> {
+        shortest_path(bytes, *n, *corner).ok_or(AocError::Unreachable("the exit"))
+    }
+
+    fn part2((bytes, corner, _): &Self::Input<'_>) -> Result<Byte, AocError> {
+        first_blocking_byte(bytes, *corner)
+            .map(Byte)
+            .ok_or_else(|| AocError::NoSolution("the exit is never cut off".to_string()))
+    }
+
+    // The memory space after the bytes of part 1 have fallen, with a shortest path through it
+    fn draw((bytes, corner, n): &Self::Input<'_>) -> Result<Option<Canvas>, AocError> {
+        let memory = corrupted(bytes, *n, *corner);
+        let path = route(&memory).ok_or(AocError::Unreachable("the exit"))?;
+        let mut canvas = Canvas::new(&memory, |&c| if c { '#' } else { '.' });
+        canvas
+            .mark(memory.positions(|&c| c), None, Color::Gray)
+            .path(&path, |p| *p, Color::Green);
+        Ok(Some(canvas))
+    }
+
+    fn report1(dist: &usize) -> String {
+        format!("The shortest path is {dist:?} steps long.")
+    }
+
+    fn report2(byte: &Byte) -> String {
+        format!("The first byte that cuts off the path to the exit is {byte}.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+    const EXAMPLE_MEMORY: (usize, usize) = (6, 6);
+
+    #[test]
+    fn part1_example() {
+        let input = Day18::parse_example(EXAMPLE).unwrap();
+        assert_eq!(input.1, EXAMPLE_MEMORY);
+        assert_eq!(Day18::part1(&input).unwrap(), 22);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day18::parse_example(EXAMPLE).unwrap();
+        let byte = Day18::part2(&input).unwrap();
+        assert_eq!(byte, Byte((6, 1).into()));
+        assert_eq!(byte.to_string(), "6,1");
+    }
+
+    #[test]
+    fn never_blocked() {
+        let (bytes, _, _) = Day18::parse_example(EXAMPLE).unwrap();
+        assert_eq!(first_blocking_byte(&bytes[..12], EXAMPLE_MEMORY), None);
+    }
+}
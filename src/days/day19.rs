@@ -0,0 +1,114 @@
+use nom::{
+    bytes::complete::tag, character::complete::alpha1, multi::separated_list1,
+    sequence::separated_pair, IResult,
+};
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::error::AocError;
+use crate::memo::Memo;
+use crate::params::Params;
+use crate::parse::{blank_line, lines, parse_all};
+use crate::solution::Solution;
+
+pub struct Day19;
+
+fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    separated_pair(
+        separated_list1(tag(", "), alpha1),
+        blank_line,
+        lines(alpha1),
+    )(input)
+}
+
+fn do_possible_arrangements<'a>(
+    design: &'a str,
+    towels: &Vec<&str>,
+    memo: &mut Memo<&'a str, usize>,
+) -> usize {
+    if design.is_empty() {
+        return 1;
+    }
+    memo.get(design, |memo| {
+        towels
+            .iter()
+            .filter(|t| design.starts_with(*t))
+            .map(|t| do_possible_arrangements(&design[t.len()..], towels, memo))
+            .sum()
+    })
+}
+
+pub fn possible_arrangements(design: &str, towels: &Vec<&str>) -> usize {
+    do_possible_arrangements(design, towels, &mut Memo::new())
+}
+
+impl Solution for Day19 {
+    type Input<'a> = (Vec<&'a str>, Vec<&'a str>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(parse_all(parse_input, input)?)
+    }
+
+    fn part1((towels, displays): &Self::Input<'_>) -> Result<usize, AocError> {
+        let rstr = format!("^({})*$", towels.iter().join("|"));
+        let re = Regex::new(&rstr).map_err(|e| AocError::InvalidInput(e.to_string()))?;
+
+        Ok(displays.iter().filter(move |d| re.is_match(d)).count())
+    }
+
+    fn part2((towels, displays): &Self::Input<'_>) -> Result<usize, AocError> {
+        Ok(displays
+            .iter()
+            .map(|d| possible_arrangements(d, towels))
+            .sum::<usize>())
+    }
+
+    fn report1(possible_count: &usize) -> String {
+        format!("There are {possible_count} possible displays")
+    }
+
+    fn report2(ways: &usize) -> String {
+        format!("The sum of distinct ways to arrange the towels is {ways}.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day19::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day19::part1(&input).unwrap(), 6);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day19::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day19::part2(&input).unwrap(), 16);
+    }
+
+    #[test]
+    fn arrangements() {
+        let (towels, _) = Day19::parse_example(EXAMPLE).unwrap();
+        assert_eq!(possible_arrangements("gbbr", &towels), 4);
+        assert_eq!(possible_arrangements("rrbgbr", &towels), 6);
+        assert_eq!(possible_arrangements("ubwu", &towels), 0);
+    }
+}
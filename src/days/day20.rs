@@ -0,0 +1,219 @@
+use std::fmt;
+
+use rayon::prelude::*;
+use serde_json::Value;
+
+use crate::bfs::bfs_all;
+use crate::dijkstra::ShortestPaths;
+use crate::error::AocError;
+use crate::grid::{neighbors, to_signed, Grid, IPoint, Metric};
+use crate::params::{Param, Params};
+use crate::render::{Canvas, Color};
+use crate::solution::{Answer, Solution};
+
+pub struct Day20;
+
+// The track, its start and its end
+type Racetrack = (Grid<char>, IPoint, IPoint);
+
+fn racetrack_neighbors(p: &IPoint, track: &Grid<char>) -> Vec<(IPoint, usize)> {
+    neighbors(p)
+        .iter()
+        .filter(|n| track.get(**n).is_some_and(|&c| c != '#'))
+        .map(|n| (*n, 1))
+        .collect::<Vec<_>>()
+}
+
+fn cheat(p: &IPoint, max_dist: usize) -> Vec<IPoint> {
+    let max_dist = max_dist as isize;
+    (-max_dist..=max_dist)
+        .flat_map(|dx| {
+            ((-max_dist + dx.abs())..=(max_dist - dx.abs())).map(move |dy| *p + (dx, dy).into())
+        })
+        .collect()
+}
+
+// Count the cheats of at most cheat_dist picoseconds that save at least cutoff. A cheat from p to c
+// takes the fastest route from the start to p, jumps to c, and then the fastest route to the end.
+fn cheats(
+    from_start: &ShortestPaths<IPoint>,
+    to_end: &ShortestPaths<IPoint>,
+    best: usize,
+    cheat_dist: usize,
+    cutoff: usize,
+) -> usize {
+    let track: Vec<_> = from_start.dists().collect();
+    track
+        .par_iter()
+        .map(|(p, to_p)| {
+            cheat(p, cheat_dist)
+                .iter()
+                .filter(|c| {
+                    to_end
+                        .dist(c)
+                        .is_some_and(|from_c| *to_p + p.manhattan(c) + from_c + cutoff <= best)
+                })
+                .count()
+        })
+        .sum::<usize>()
+}
+
+// Count the cheats of at most cheat_dist picoseconds that save at least cutoff picoseconds
+pub fn count_cheats(
+    (track, start, end): &Racetrack,
+    cheat_dist: usize,
+    cutoff: usize,
+) -> Result<usize, AocError> {
+    let from_start = bfs_all(&[*start], |p| racetrack_neighbors(p, track));
+    let to_end = bfs_all(&[*end], |p| racetrack_neighbors(p, track));
+    let best = from_start
+        .dist(end)
+        .ok_or(AocError::Unreachable("the end"))?;
+
+    Ok(cheats(&from_start, &to_end, best, cheat_dist, cutoff))
+}
+
+// How many cheats of some length save at least the cutoff
+pub struct Cheats {
+    length: usize,
+    cutoff: usize,
+    count: usize,
+}
+
+impl Cheats {
+    fn count(racetrack: &Racetrack, length: usize, cutoff: usize) -> Result<Self, AocError> {
+        Ok(Cheats {
+            length,
+            cutoff,
+            count: count_cheats(racetrack, length, cutoff)?,
+        })
+    }
+}
+
+impl fmt::Display for Cheats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)
+    }
+}
+
+impl Answer for Cheats {
+    fn to_json(&self) -> Value {
+        self.count.to_json()
+    }
+}
+
+fn report(cheats: &Cheats) -> String {
+    let Cheats {
+        length,
+        cutoff,
+        count,
+    } = cheats;
+    format!("Number of length {length} or less cheats that save {cutoff} ps or more: {count}.")
+}
+
+impl Solution for Day20 {
+    // The racetrack and the least time a cheat must save
+    type Input<'a> = (Racetrack, usize);
+    type Answer1 = Cheats;
+    type Answer2 = Cheats;
+
+    const PARAMS: &'static [Param] = &[Param::profiles("cutoff", "50", "100")];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        let track: Grid<char> = input.parse()?;
+        let start = to_signed(
+            track
+                .find(&'S')
+                .ok_or(AocError::MissingMarker("start position"))?,
+        );
+        let end = to_signed(
+            track
+                .find(&'E')
+                .ok_or(AocError::MissingMarker("end position"))?,
+        );
+        Ok(((track, start, end), params.get("cutoff")?))
+    }
+
+    fn part1((racetrack, cutoff): &Self::Input<'_>) -> Result<Cheats, AocError> {
+        Cheats::count(racetrack, 2, *cutoff)
+    }
+
+    fn part2((racetrack, cutoff): &Self::Input<'_>) -> Result<Cheats, AocError> {
+        Cheats::count(racetrack, 20, *cutoff)
+    }
+
+    // The racetrack with the fastest route along it, not counting cheats
+    fn draw(((track, start, end), _): &Self::Input<'_>) -> Result<Option<Canvas>, AocError> {
+        let route = bfs_all(&[*start], |p| racetrack_neighbors(p, track))
+            .get(end)
+            .ok_or(AocError::Unreachable("the end"))?;
+        let mut canvas = Canvas::from(track);
+        canvas
+            .mark(track.positions(|&c| c == '#'), None, Color::Gray)
+            .path(&route, |p| *p, Color::Green)
+            .mark([*start], Some('S'), Color::Red)
+            .mark([*end], Some('E'), Color::Red);
+        Ok(Some(canvas))
+    }
+
+    fn report1(cheats: &Cheats) -> String {
+        report(cheats)
+    }
+
+    fn report2(cheats: &Cheats) -> String {
+        report(cheats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[test]
+    fn examples_with_cutoff() {
+        let input = Day20::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day20::part1(&input).unwrap().count, 1);
+        assert_eq!(Day20::part2(&input).unwrap().count, 285);
+    }
+
+    #[test]
+    fn short_cheats() {
+        let (input, _) = Day20::parse_example(EXAMPLE).unwrap();
+        assert_eq!(count_cheats(&input, 2, 2).unwrap(), 44);
+        assert_eq!(count_cheats(&input, 2, 64).unwrap(), 1);
+    }
+
+    #[test]
+    fn long_cheats() {
+        let (input, _) = Day20::parse_example(EXAMPLE).unwrap();
+        assert_eq!(count_cheats(&input, 20, 50).unwrap(), 285);
+        assert_eq!(count_cheats(&input, 20, 76).unwrap(), 3);
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let (input, _) = Day20::parse_example("#####\n#S#E#\n#####\n").unwrap();
+        assert!(matches!(
+            count_cheats(&input, 2, 1),
+            Err(AocError::Unreachable(_))
+        ));
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::once;
+
+use itertools::{chain, iproduct, repeat_n, Itertools};
+
+//use rayon::prelude::*;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, u64},
+    combinator::{peek, value},
+    multi::many1,
+    sequence::terminated,
+    IResult, Parser,
+};
+
+use serde_json::Value;
+
+use crate::error::AocError;
+use crate::memo::Memo;
+use crate::params::{Param, Params};
+use crate::parse::{lines, parse_all};
+use crate::solution::{Answer, Solution};
+use crate::vector::Vector2;
+
+pub struct Day21;
+
+type Coordinates = Vector2<i8>;
+
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum NumericKeypad {
+    A,
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+}
+
+trait Keyboard: Sized {
+    fn coordinates(&self) -> Coordinates;
+    fn from_coordinates(c: Coordinates) -> Option<Self>;
+}
+
+impl Keyboard for NumericKeypad {
+    fn coordinates(&self) -> Coordinates {
+        use NumericKeypad::*;
+        match self {
+            A => (2, 0),
+            Zero => (1, 0),
+            One => (0, 1),
+            Two => (1, 1),
+            Three => (2, 1),
+            Four => (0, 2),
+            Five => (1, 2),
+            Six => (2, 2),
+            Seven => (0, 3),
+            Eight => (1, 3),
+            Nine => (2, 3),
+        }
+        .into()
+    }
+    fn from_coordinates(Coordinates { x, y }: Coordinates) -> Option<Self> {
+        use NumericKeypad::*;
+        match (x, y) {
+            (2, 0) => Some(A),
+            (1, 0) => Some(Zero),
+            (0, 1) => Some(One),
+            (1, 1) => Some(Two),
+            (2, 1) => Some(Three),
+            (0, 2) => Some(Four),
+            (1, 2) => Some(Five),
+            (2, 2) => Some(Six),
+            (0, 3) => Some(Seven),
+            (1, 3) => Some(Eight),
+            (2, 3) => Some(Nine),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum DirectionalKeypad {
+    A,
+    Up,
+    Left,
+    Down,
+    Right,
+}
+
+impl Keyboard for DirectionalKeypad {
+    fn coordinates(&self) -> Coordinates {
+        use DirectionalKeypad::*;
+        (match self {
+            Up => (1, 1),
+            A => (2, 1),
+            Left => (0, 0),
+            Down => (1, 0),
+            Right => (2, 0),
+        })
+        .into()
+    }
+    fn from_coordinates(Coordinates { x, y }: Coordinates) -> Option<Self> {
+        use DirectionalKeypad::*;
+        match (x, y) {
+            (1, 1) => Some(Up),
+            (2, 1) => Some(A),
+            (0, 0) => Some(Left),
+            (1, 0) => Some(Down),
+            (2, 0) => Some(Right),
+            _ => None,
+        }
+    }
+}
+
+impl DirectionalKeypad {
+    // How far pressing this key moves the arm of the keypad it controls
+    fn offset(&self) -> Coordinates {
+        use DirectionalKeypad::*;
+        (match self {
+            Up => (0, 1),
+            Down => (0, -1),
+            Left => (-1, 0),
+            Right => (1, 0),
+            A => (0, 0),
+        })
+        .into()
+    }
+}
+
+// Every shortest sequence of arrow presses moving the arm from a to b without leaving the keypad
+fn paths<T: Keyboard>(a: &T, b: &T) -> Vec<Vec<DirectionalKeypad>> {
+    use DirectionalKeypad::*;
+    let delta = b.coordinates() - a.coordinates();
+    let Coordinates { x: dx, y: dy } = delta;
+    let xs = repeat_n(
+        if dx >= 0 { Right } else { Left },
+        dx.unsigned_abs() as usize,
+    );
+    let ys = repeat_n(if dy >= 0 { Up } else { Down }, dy.unsigned_abs() as usize);
+    chain!(xs, ys)
+        .permutations(delta.manhattan_norm())
+        .filter(|p| {
+            p.iter()
+                .scan(a.coordinates(), |acc, k| {
+                    *acc += k.offset();
+                    Some(*acc)
+                })
+                .all(|c| T::from_coordinates(c).is_some())
+        })
+        .unique()
+        .collect()
+}
+
+#[allow(dead_code)]
+fn dir_path_to_str(p: &[DirectionalKeypad]) -> String {
+    use DirectionalKeypad::*;
+    p.iter()
+        .map(|q| match q {
+            Up => "^",
+            Down => "v",
+            Right => ">",
+            Left => "<",
+            A => "A",
+        })
+        .join("")
+}
+
+// The presses that move the arm along p and then press the key it ends up on
+fn path_to_dir(p: &[DirectionalKeypad]) -> Vec<DirectionalKeypad> {
+    chain![p.iter().copied(), once(DirectionalKeypad::A)].collect()
+}
+
+fn numeric_to_directional(
+    s: &[NumericKeypad],
+    num_paths: &BTreeMap<(NumericKeypad, NumericKeypad), Vec<Vec<DirectionalKeypad>>>,
+) -> Vec<Vec<DirectionalKeypad>> {
+    chain![once(&NumericKeypad::A), s.iter()]
+        .tuple_windows()
+        .map(|(a, b)| num_paths.get(&(*a, *b)).unwrap().iter())
+        .multi_cartesian_product()
+        .map(|ps| {
+            ps.iter()
+                .map(|p| path_to_dir(p))
+                .fold(Vec::new(), |mut v, p| {
+                    v.extend(p.iter());
+                    //v.push(DirectionalKeypad::A);
+                    v
+                })
+        })
+        .collect_vec()
+}
+pub fn directional_to_directional(
+    s: &[DirectionalKeypad],
+    dir_paths: &BTreeMap<(DirectionalKeypad, DirectionalKeypad), Vec<Vec<DirectionalKeypad>>>,
+    depth: usize,
+    memo: &mut Memo<(Vec<DirectionalKeypad>, usize), usize>,
+) -> usize {
+    // With no more keypads to go through, the presses are typed directly
+    if depth == 0 {
+        return s.len();
+    }
+    memo.get((s.to_vec(), depth), |memo| {
+        chain![once(&DirectionalKeypad::A), s.iter()]
+            .tuple_windows()
+            .map(|(a, b)| {
+                let paths = dir_paths.get(&(*a, *b)).unwrap();
+                if depth == 1 {
+                    paths.iter().map(Vec::len).min().unwrap()
+                } else {
+                    paths
+                        .iter()
+                        .map(|p| directional_to_directional(p, dir_paths, depth - 1, memo))
+                        .min()
+                        .unwrap()
+                }
+            })
+            .sum::<usize>()
+    })
+}
+
+fn parse_numeric_kbd(input: &str) -> IResult<&str, (Vec<NumericKeypad>, u64)> {
+    use NumericKeypad::*;
+    peek(many1(alt((
+        value(A, char('A')),
+        value(Zero, char('0')),
+        value(One, char('1')),
+        value(Two, char('2')),
+        value(Three, char('3')),
+        value(Four, char('4')),
+        value(Five, char('5')),
+        value(Six, char('6')),
+        value(Seven, char('7')),
+        value(Eight, char('8')),
+        value(Nine, char('9')),
+    ))))
+    .and(terminated(u64, char('A')))
+    .parse(input)
+}
+
+pub struct Codes {
+    pub codes: Vec<(Vec<NumericKeypad>, u64)>,
+    pub num_paths: BTreeMap<(NumericKeypad, NumericKeypad), Vec<Vec<DirectionalKeypad>>>,
+    pub dir_paths: BTreeMap<(DirectionalKeypad, DirectionalKeypad), Vec<Vec<DirectionalKeypad>>>,
+    // The number of directional keypads in the chain for each part
+    pub robots: (usize, usize),
+}
+
+pub fn total_complexity(codes: &Codes, n_directionals: usize) -> usize {
+    let mut memo = Memo::new();
+    let mut total_complexity = 0;
+    for (n_sequence, prefix) in codes.codes.iter() {
+        let dir_sequences = numeric_to_directional(n_sequence, &codes.num_paths);
+
+        let shortest = dir_sequences
+            .iter()
+            // minus one because one of the directional keyboard iterations was already taken care
+            // of transforming from the numerical keyboard
+            .map(|s| directional_to_directional(s, &codes.dir_paths, n_directionals - 1, &mut memo))
+            .min()
+            .unwrap();
+        let complexity = shortest * (*prefix as usize);
+        total_complexity += complexity;
+    }
+    total_complexity
+}
+
+pub struct Complexity {
+    robots: usize,
+    total: usize,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.total)
+    }
+}
+
+impl Answer for Complexity {
+    fn to_json(&self) -> Value {
+        self.total.to_json()
+    }
+}
+
+impl Solution for Day21 {
+    type Input<'a> = Codes;
+    type Answer1 = Complexity;
+    type Answer2 = Complexity;
+
+    const PARAMS: &'static [Param] = &[Param::new("robots1", "3"), Param::new("robots2", "26")];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        // Precompute all shortest paths on both types of keyboards
+        let num_keys = {
+            use NumericKeypad::*;
+            vec![
+                A, Zero, One, Two, Three, Four, Five, Six, Seven, Eight, Nine,
+            ]
+        };
+        let num_paths = BTreeMap::from_iter(
+            iproduct![num_keys.iter(), num_keys.iter()].map(|(a, b)| ((*a, *b), paths(a, b))),
+        );
+        let dir_keys = {
+            use DirectionalKeypad::*;
+            vec![A, Up, Left, Down, Right]
+        };
+        let dir_paths =
+            BTreeMap::from_iter(iproduct![dir_keys.iter(), dir_keys.iter()].map(|(a, b)| {
+                (
+                    (*a, *b),
+                    paths(a, b).iter().map(|p| path_to_dir(p)).collect(),
+                )
+            }));
+
+        let codes = parse_all(lines(parse_numeric_kbd), input)?;
+        // The last robot in the chain types on the numeric keypad, so there is always at least one
+        let robots = |name: &str| match params.get::<usize>(name)? {
+            0 => Err(AocError::InvalidParam(format!("{name} must be at least 1"))),
+            n => Ok(n),
+        };
+        Ok(Codes {
+            codes,
+            num_paths,
+            dir_paths,
+            robots: (robots("robots1")?, robots("robots2")?),
+        })
+    }
+
+    fn part1(codes: &Self::Input<'_>) -> Result<Complexity, AocError> {
+        let robots = codes.robots.0;
+        Ok(Complexity {
+            robots,
+            total: total_complexity(codes, robots),
+        })
+    }
+
+    fn part2(codes: &Self::Input<'_>) -> Result<Complexity, AocError> {
+        let robots = codes.robots.1;
+        Ok(Complexity {
+            robots,
+            total: total_complexity(codes, robots),
+        })
+    }
+
+    fn report1(Complexity { robots, total }: &Complexity) -> String {
+        format!("Total complexity of the five codes ({robots} robots): {total}")
+    }
+
+    fn report2(Complexity { robots, total }: &Complexity) -> String {
+        format!("Total complexity of the five codes ({robots} robots): {total}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+029A
+980A
+179A
+456A
+379A
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day21::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day21::part1(&input).unwrap().total, 126384);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day21::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day21::part2(&input).unwrap().total, 154115708116294);
+    }
+
+    #[test]
+    fn directional_keypads() {
+        use DirectionalKeypad::*;
+        let codes = Day21::parse_example(EXAMPLE).unwrap();
+        // <A^A>^^AvvvA, the presses that type 029A on the numeric keypad
+        let presses = vec![Left, A, Up, A, Right, Up, Up, A, Down, Down, Down, A];
+        let mut memo = Memo::new();
+        assert_eq!(
+            directional_to_directional(&presses, &codes.dir_paths, 1, &mut memo),
+            28
+        );
+        assert_eq!(
+            directional_to_directional(&presses, &codes.dir_paths, 2, &mut memo),
+            68
+        );
+    }
+
+    #[test]
+    fn one_robot() {
+        // The only robot types on the numeric keypad, so the presses are <A^A>^^AvvvA
+        let mut input = Day21::parse_example("029A\n").unwrap();
+        input.robots = (1, 1);
+        assert_eq!(Day21::part1(&input).unwrap().total, 12 * 29);
+    }
+}
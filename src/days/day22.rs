@@ -0,0 +1,195 @@
+use std::fmt;
+
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::solution::{Answer, Solution};
+
+pub struct Day22;
+
+type DiffSeq = (i64, i64, i64, i64);
+
+fn mix_and_prune(x: u64, y: u64) -> u64 {
+    (x ^ y) % 16777216
+}
+
+pub fn secret(x: u64) -> u64 {
+    let y = mix_and_prune(x * 64, x);
+    let z = mix_and_prune(y / 32, y);
+    mix_and_prune(z * 2048, z)
+}
+
+fn secrets(seed: u64, n_folds: usize) -> impl Iterator<Item = u64> {
+    (0..n_folds).scan(seed, |state, _| {
+        let prev = *state;
+        *state = secret(*state);
+        Some(prev % 10)
+    })
+}
+
+fn buys(secrets: &[u64]) -> BTreeMap<DiffSeq, u64> {
+    let diffs = secrets
+        .iter()
+        .tuple_windows()
+        .map(|(y, x)| x.wrapping_sub(*y) as i64)
+        .tuple_windows::<DiffSeq>();
+    let prices = Iterator::zip(diffs, secrets.iter().skip(4));
+    let mut map = BTreeMap::new();
+    for (k, v) in prices {
+        // Only the first time a sequence is seen counts, as the monkey sells straight away
+        map.entry(k).or_insert(*v);
+    }
+    map
+}
+
+fn sum_by_key(prices: &Vec<BTreeMap<DiffSeq, u64>>) -> BTreeMap<DiffSeq, u64> {
+    let mut out = BTreeMap::new();
+    for ps in prices {
+        for (&k, &v) in ps {
+            let r = out.entry(k).or_insert(0);
+            *r += v;
+        }
+    }
+    out
+}
+
+pub fn maximize_buys(seeds: &[u64], n_folds: usize) -> (u64, Option<DiffSeq>) {
+    let secrets: Vec<Vec<u64>> = seeds
+        .par_iter()
+        .map(|&s| secrets(s, n_folds).collect_vec())
+        .collect();
+
+    let m = secrets.par_iter().map(|s| buys(s)).collect();
+    let summed = sum_by_key(&m);
+    let m = summed.iter().max_by_key(|(_, v)| *v);
+    match m {
+        Some((seq, v)) => (*v, Some(*seq)),
+        None => (0, None),
+    }
+}
+
+pub struct SecretSum {
+    folds: usize,
+    sum: u64,
+}
+
+impl fmt::Display for SecretSum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sum)
+    }
+}
+
+impl Answer for SecretSum {
+    fn to_json(&self) -> Value {
+        self.sum.to_json()
+    }
+}
+
+pub struct BestBuy {
+    bananas: u64,
+    seq: DiffSeq,
+}
+
+impl fmt::Display for BestBuy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bananas)
+    }
+}
+
+impl Answer for BestBuy {
+    fn to_json(&self) -> Value {
+        self.bananas.to_json()
+    }
+}
+
+impl Solution for Day22 {
+    // Seeds, and how many secret numbers each buyer generates
+    type Input<'a> = (Vec<u64>, usize);
+    type Answer1 = SecretSum;
+    type Answer2 = BestBuy;
+
+    const PARAMS: &'static [Param] = &[Param::new("folds", "2000")];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        let seeds = input.lines().map(|s| s.parse()).try_collect()?;
+        Ok((seeds, params.get("folds")?))
+    }
+
+    fn part1((seeds, folds): &Self::Input<'_>) -> Result<SecretSum, AocError> {
+        let sum = seeds
+            .iter()
+            .map(|x| (0..*folds).fold(*x, |s, _| secret(s)))
+            .sum();
+        Ok(SecretSum { folds: *folds, sum })
+    }
+
+    fn part2((seeds, folds): &Self::Input<'_>) -> Result<BestBuy, AocError> {
+        let (bananas, seq) = maximize_buys(seeds, *folds);
+        let seq = seq.ok_or_else(|| {
+            AocError::NoSolution("no sequence of changes is ever seen (empty input?)".to_string())
+        })?;
+        Ok(BestBuy { bananas, seq })
+    }
+
+    fn report1(SecretSum { folds, sum }: &SecretSum) -> String {
+        format!("Sum of {folds}th secret numbers: {sum}.")
+    }
+
+    fn report2(BestBuy { bananas, seq }: &BestBuy) -> String {
+        format!("To maximize buys, use: {seq:?} ({bananas} bananas)")
+    }
+
+    fn extras1(SecretSum { folds, .. }: &SecretSum) -> Option<Value> {
+        Some(json!({ "folds": folds }))
+    }
+
+    fn extras2(BestBuy { seq, .. }: &BestBuy) -> Option<Value> {
+        let (a, b, c, d) = seq;
+        Some(json!({ "sequence": [a, b, c, d] }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let input = Day22::parse_example(
+            "\
+1
+10
+100
+2024
+",
+        )
+        .unwrap();
+        assert_eq!(Day22::part1(&input).unwrap().sum, 37327623);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            maximize_buys(&[1, 2, 3, 2024], 2000),
+            (23, Some((-2, 1, -1, 3)))
+        );
+    }
+
+    #[test]
+    fn first_sale_counts() {
+        // The changes 1, 1, -2, 0 come round twice, selling for 0 and then for 3
+        let seen = buys(&[0, 1, 2, 0, 0, 3, 4, 5, 3, 3]);
+        assert_eq!(seen[&(1, 1, -2, 0)], 0);
+        assert_eq!(seen.len(), 5);
+    }
+
+    #[test]
+    fn secret_sequence() {
+        assert_eq!(secret(123), 15887950);
+        assert_eq!(secret(15887950), 16495136);
+    }
+}
@@ -0,0 +1,179 @@
+use itertools::Itertools;
+use nom::{
+    character::complete::{alpha1, char},
+    sequence::separated_pair,
+    IResult,
+};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::error::AocError;
+use crate::memo::Memo;
+use crate::params::{Param, Params};
+use crate::parse::{lines, parse_all};
+use crate::solution::{Answer, Solution};
+
+pub struct Day23;
+
+type Lan<'a> = BTreeMap<&'a str, BTreeSet<&'a str>>;
+type Cliques<'a> = BTreeSet<BTreeSet<&'a str>>;
+type CliqueMemo<'a> = Memo<(&'a str, usize), Cliques<'a>, BTreeMap<(&'a str, usize), Cliques<'a>>>;
+
+fn parse_lan(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
+    lines(separated_pair(alpha1, char('-'), alpha1))(input)
+}
+
+fn cliques<'a>(
+    c: &'a str,
+    lan: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+    depth: usize,
+    memo: &mut CliqueMemo<'a>,
+) -> Cliques<'a> {
+    memo.get((c, depth), |memo| {
+        if depth == 0 {
+            return BTreeSet::from_iter(vec![BTreeSet::from_iter(std::iter::once(c))]);
+        }
+        let mut out = BTreeSet::new();
+        for n in lan.get(c).unwrap() {
+            let prev = cliques(n, lan, depth - 1, memo);
+            for cli in prev {
+                if cli.iter().all(|m| lan.get(m).unwrap().contains(c)) {
+                    let mut new = cli.clone();
+                    new.insert(c);
+                    out.insert(new);
+                }
+            }
+        }
+        out
+    })
+}
+
+pub struct Triangles {
+    initial: char,
+    count: usize,
+}
+
+impl fmt::Display for Triangles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)
+    }
+}
+
+impl Answer for Triangles {
+    fn to_json(&self) -> Value {
+        self.count.to_json()
+    }
+}
+
+impl Solution for Day23 {
+    // The network, its computers, and the initial of the computers part 1 looks for
+    type Input<'a> = (Lan<'a>, BTreeSet<&'a str>, char);
+    type Answer1 = Triangles;
+    type Answer2 = String;
+
+    const PARAMS: &'static [Param] = &[Param::new("initial", "t")];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError> {
+        let mut lan: Lan = BTreeMap::new();
+        let mut computers: BTreeSet<&str> = BTreeSet::new();
+        let parsed = parse_all(parse_lan, input)?;
+        for (l, r) in parsed {
+            lan.entry(l).or_default().insert(r);
+            computers.insert(l);
+            lan.entry(r).or_default().insert(l);
+            computers.insert(r);
+        }
+        Ok((lan, computers, params.get("initial")?))
+    }
+
+    fn part1((lan, computers, initial): &Self::Input<'_>) -> Result<Triangles, AocError> {
+        let mut memo = Memo::new();
+        let n_cliques = computers
+            .iter()
+            .filter(|c| c.starts_with(*initial))
+            .flat_map(|c| cliques(c, lan, 2, &mut memo))
+            .collect::<BTreeSet<_>>();
+        Ok(Triangles {
+            initial: *initial,
+            count: n_cliques.len(),
+        })
+    }
+
+    fn part2((lan, computers, _): &Self::Input<'_>) -> Result<String, AocError> {
+        let mut memo = Memo::new();
+        let mut largest_clique = BTreeSet::<&str>::new();
+        for c in computers.iter() {
+            let max_depth = lan.get(c).unwrap().len();
+            let largest = (0..max_depth)
+                .flat_map(|d| cliques(c, lan, d, &mut memo))
+                .max_by_key(|cl| cl.len());
+            if let Some(lrg) = largest.filter(|lrg| lrg.len() > largest_clique.len()) {
+                largest_clique = lrg;
+            }
+        }
+        Ok(largest_clique.iter().join(","))
+    }
+
+    fn report1(Triangles { initial, count }: &Triangles) -> String {
+        format!("Number of 3-cliques with at least one computer beginning with {initial}: {count}")
+    }
+
+    fn report2(password: &String) -> String {
+        format!("LAN password: {password}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day23::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day23::part1(&input).unwrap().count, 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day23::parse_example(EXAMPLE).unwrap();
+        assert_eq!(Day23::part2(&input).unwrap(), "co,de,ka,ta");
+    }
+}
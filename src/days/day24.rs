@@ -0,0 +1,413 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use std::collections::BTreeMap;
+
+use itertools::iproduct;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, space1},
+    combinator::{map, value},
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+
+use crate::error::AocError;
+use crate::graph::Graph;
+use crate::params::Params;
+use crate::parse::{blank_line, key_value, lines, parse_all};
+use crate::solution::Solution;
+
+pub struct Day24;
+
+// DATA TYPES
+
+#[derive(Copy, Clone)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+// Enum for the Components of a circuit, i.e. Gates and incoming Wires
+// The Rc<RefCell<_>> pattern allows mutable access to the Components.
+type ComponentRef = Rc<RefCell<Component>>;
+enum Component {
+    Wire {
+        value: bool,
+    },
+    Gate {
+        left: ComponentRef,
+        right: ComponentRef,
+        op: Op,
+    },
+}
+// A backtrace from a single Component can be easily built by tree traversal, but if we want the
+// full DAG we'll need to put the Components into something that can be indexed
+type Netlist<'a> = BTreeMap<&'a str, ComponentRef>;
+
+impl Component {
+    // Set the value of a Wire. Does nothing if the Component is a Gate.
+    fn set(&mut self, new: bool) {
+        if let Component::Wire { value } = self {
+            *value = new;
+        }
+    }
+
+    fn eval(&self) -> bool {
+        use Component::*;
+        match self {
+            Wire { value } => *value,
+            Gate { left, right, op } => {
+                // If the upstream values could not change, it would make sense to cache the result
+                // of eval() in the Gate itself. Because upstream values *are* mutable, we
+                // cannot do this and must re-evaluate the whole chain anew on each call.
+                // Of course, cache invalidation could be implemented.
+                let (lv, rv) = (left.borrow().eval(), right.borrow().eval());
+                match op {
+                    Op::Or => lv || rv,
+                    Op::And => lv && rv,
+                    Op::Xor => lv ^ rv,
+                }
+            }
+        }
+    }
+}
+
+// A RawComponent is a Component referring to its inputs by their names
+pub enum RawComponent<'a> {
+    Wire(&'a str, bool),
+    Gate(&'a str, &'a str, Op, &'a str),
+}
+impl<'a> RawComponent<'a> {
+    fn name(&self) -> &'a str {
+        match self {
+            RawComponent::Wire(n, _) => n,
+            RawComponent::Gate(n, _, _, _) => n,
+        }
+    }
+}
+type RawNetlist<'a> = BTreeMap<&'a str, RawComponent<'a>>;
+
+// PARSERS
+
+fn parse_wire(input: &str) -> IResult<&str, RawComponent<'_>> {
+    map(
+        key_value(
+            alphanumeric1,
+            alt((value(false, char('0')), value(true, char('1')))),
+        ),
+        |(n, v)| RawComponent::Wire(n, v),
+    )(input)
+}
+
+fn parse_op(input: &str) -> IResult<&str, Op> {
+    alt((
+        value(Op::And, tag("AND")),
+        value(Op::Or, tag("OR")),
+        value(Op::Xor, tag("XOR")),
+    ))(input)
+}
+
+fn parse_gate(input: &str) -> IResult<&str, RawComponent<'_>> {
+    map(
+        separated_pair(
+            tuple((alphanumeric1, space1, parse_op, space1, alphanumeric1)),
+            delimited(space1, tag("->"), space1),
+            alphanumeric1,
+        ),
+        |((l, _, op, _, r), out)| RawComponent::Gate(out, l, op, r),
+    )(input)
+}
+
+// The initial wire values, then a blank line, then the gates
+fn parse_rawnetlist(input: &str) -> IResult<&str, RawNetlist<'_>> {
+    map(
+        separated_pair(lines(parse_wire), blank_line, lines(parse_gate)),
+        |(wires, gates)| {
+            wires
+                .into_iter()
+                .chain(gates)
+                .map(|c| (c.name(), c))
+                .collect()
+        },
+    )(input)
+}
+
+// LIBRARY
+
+// Topologically sort a RawNetlist, i.e. a graph of gates and wires identified by strings, so that
+// every gate comes after its inputs
+fn sort_topologically<'a>(
+    netlist: &'a RawNetlist<'a>,
+) -> Result<Vec<&'a RawComponent<'a>>, AocError> {
+    let mut graph = Graph::new();
+    for (&name, c) in netlist {
+        graph.add_node(name);
+        if let RawComponent::Gate(out, left, _, right) = c {
+            graph.add_edge(*left, *out);
+            graph.add_edge(*right, *out);
+        }
+    }
+    let order = graph
+        .topological_sort_dfs()
+        .map_err(|cycle| AocError::InvalidInput(format!("the gates form a loop: {cycle}")))?;
+    // Inputs that aren't in the netlist are left for build_netlist to report
+    Ok(order.into_iter().filter_map(|n| netlist.get(n)).collect())
+}
+
+// Build a RawNetlist into a Netlist, i.e., a graph of gates and wires connected by Rc<RefCell<_>>:s
+fn build_netlist<'a>(raw: &'a RawNetlist<'a>) -> Result<Netlist<'a>, AocError> {
+    // The Netlist cannot be built with try_collect() because the dependencies of a node must exist
+    // in the netlist before that node can be inserted, but it can be built with
+    // try_fold()!
+    let to_rcrc = |c| Rc::new(RefCell::new(c));
+    let work = |mut netlist: Netlist<'a>, c: &RawComponent<'a>| {
+        let get_or_err = |v, out| {
+            netlist
+                .get(v)
+                .ok_or_else(|| {
+                    AocError::InvalidInput(format!(
+                        "{out} needs input {v}, which is not in the netlist"
+                    ))
+                })
+                .map(Rc::clone)
+        };
+        match c {
+            RawComponent::Wire(n, v) => netlist.insert(n, to_rcrc(Wire { value: *v })),
+            RawComponent::Gate(out, l, op, r) => netlist.insert(
+                out,
+                to_rcrc(Gate {
+                    left: get_or_err(l, out)?,
+                    right: get_or_err(r, out)?,
+                    op: *op,
+                }),
+            ),
+        };
+        Ok(netlist)
+    };
+
+    use Component::*;
+    sort_topologically(raw)?
+        .into_iter()
+        .try_fold(Netlist::new(), work)
+}
+
+// Read the number on the z wires, returning it along with the number of output bits
+fn output_number(netlist: &Netlist) -> (u64, usize) {
+    let mut out = 0;
+    let mut n_bits_out = 0;
+    for (n, c) in netlist.iter().rev() {
+        if n.starts_with('z') {
+            out <<= 1;
+            let v = c.borrow().eval();
+            out |= v as u64;
+            n_bits_out += 1;
+        }
+    }
+    (out, n_bits_out)
+}
+
+impl Solution for Day24 {
+    type Input<'a> = RawNetlist<'a>;
+    type Answer1 = u64;
+    type Answer2 = String;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(parse_all(parse_rawnetlist, input)?)
+    }
+
+    fn part1(raw_netlist: &Self::Input<'_>) -> Result<u64, AocError> {
+        let netlist = build_netlist(raw_netlist)?;
+        Ok(output_number(&netlist).0)
+    }
+
+    fn part2(raw_netlist: &Self::Input<'_>) -> Result<String, AocError> {
+        let netlist = build_netlist(raw_netlist)?;
+        let (_, n_bits_out) = output_number(&netlist);
+
+        /* To solve part 2:
+         * The code below tries adding (a << n) + (b << n) where a and b are 0 or 1.
+         * This works because:
+         *     - The k first bits of adding two n-bit numbers depend only on the first k bits of each
+         *     number
+         *     - The (n+1)th bit of (x + y), where x and y are n bits wide, depends on:
+         *         - The n:th bit of (x_t + y_t) where x_t is x truncated to n-1 bits
+         *         - The n:th bits of x and y
+         *       To realize this, write x = x_n .. x_0, y = y_0 ... y 0
+         *       Now x+y = x_n 000.. + y_n 000 + (x_{n-1} .. x_0 + y_{n-1} ... y_0);
+         *       the two most significant bits are (x_n + y_n + (x+y)_{n})
+         *     - Therefore, if the adder is correct when adding (n-1)-bit numbers AND it correctly adds
+         *     (a << n) + (b << n) for all values of a and b, it is correct for n-bit numbers.
+         *  The first incorrect output bit is returned as an AocError::NoSolution holding the
+         *  failing sum, and an empty answer means the adder is wired correctly.
+         *  The general structure of the adder looks like this
+         *  A      XOR B        -> z(N-1)
+         *  A      AND B        -> C
+         *  x(N-1) XOR  y(N-1)  -> D
+         *  C OR D              -> E
+         *  xN     XOR yN       -> F
+         *  E XOR F             -> zN
+         *  If part 2 reports that bit N is incorrect but bit N-1 was correct, start by identifying
+         *  A and B, then follow the trace trying to find zN.
+         *  Correct the wiring in the input file, then run part 2 again.
+         *  Repeat until it returns an answer instead of an incorrect bit.
+         *  To put the answer into the format asked for, `diff` the edited file against the original
+         *  input (or, log your edits (e.g, on paper) as you do them), then run your favourite sorting
+         *  algorithm.
+         */
+
+        // First reset every input bit to 0
+        for i in 0..n_bits_out {
+            if let Some(w) = netlist.get(format!("x{i:02}").as_str()) {
+                w.borrow_mut().set(false);
+            }
+            if let Some(w) = netlist.get(format!("y{i:02}").as_str()) {
+                w.borrow_mut().set(false);
+            }
+        }
+
+        for i in 1..n_bits_out {
+            let set_wire = |n, v| netlist.get(n).map(|w| w.borrow_mut().set(v));
+            let get = |n| netlist.get(n).map(|w| w.borrow().eval());
+
+            let high = format!("z{i:02}");
+            let low = format!("z{:02}", i - 1);
+            let x = format!("x{:02}", i - 1);
+            let y = format!("y{:02}", i - 1);
+
+            for (xv, yv) in iproduct![vec![false, true], vec![false, true]] {
+                set_wire(x.as_str(), xv);
+                set_wire(y.as_str(), yv);
+
+                let low_v = get(low.as_str()).ok_or_else(|| {
+                    AocError::InvalidInput(format!("no wire {low} in the netlist"))
+                })? as u8;
+                let high_v = get(high.as_str()).ok_or_else(|| {
+                    AocError::InvalidInput(format!("no wire {high} in the netlist"))
+                })? as u8;
+                let expected = (xv as u8) + (yv as u8);
+                let out = (high_v << 1) + low_v;
+                if out != expected {
+                    return Err(AocError::NoSolution(format!(
+                        "Incorrect result of ({} << {shift}) + ({} << {shift})\nGot 0b{out:02b} << {shift}, expected 0b{expected:02b} << {shift}",
+                        xv as u8,
+                        yv as u8,
+                        shift = i - 1,
+                    )));
+                }
+            }
+
+            // Reset for the next iteration
+            set_wire(x.as_str(), false);
+            set_wire(y.as_str(), false);
+        }
+
+        // No wires need swapping
+        Ok(String::new())
+    }
+
+    fn report1(out: &u64) -> String {
+        format!("Output number: {out}")
+    }
+
+    fn report2(swapped: &String) -> String {
+        if swapped.is_empty() {
+            "The adder is wired correctly".to_string()
+        } else {
+            format!("Swapped wires: {swapped}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let input = Day24::parse_example(
+            "\
+x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj
+",
+        )
+        .unwrap();
+        assert_eq!(Day24::part1(&input).unwrap(), 2024);
+    }
+
+    #[test]
+    fn gate_loops() {
+        let input = Day24::parse_example("x00: 1\n\nx00 OR b -> a\na AND x00 -> b\n").unwrap();
+        let Err(AocError::InvalidInput(msg)) = Day24::part1(&input) else {
+            panic!("a loop of gates can't be evaluated");
+        };
+        assert_eq!(msg, "the gates form a loop: a -> b -> a");
+        let input = Day24::parse_example("x00: 1\n\nx00 OR y00 -> z00\n").unwrap();
+        assert!(Day24::part1(&input).is_err());
+    }
+
+    #[test]
+    fn miswired_adder() {
+        let half_adder = "x00: 0\ny00: 0\n\nx00 XOR y00 -> z00\nx00 AND y00 -> z01\n";
+        let input = Day24::parse_example(half_adder).unwrap();
+        assert_eq!(Day24::part2(&input).unwrap(), "");
+
+        let swapped = "x00: 0\ny00: 0\n\nx00 AND y00 -> z00\nx00 XOR y00 -> z01\n";
+        let input = Day24::parse_example(swapped).unwrap();
+        let Err(AocError::NoSolution(diagnosis)) = Day24::part2(&input) else {
+            panic!("the outputs of the half adder are swapped");
+        };
+        assert_eq!(
+            diagnosis,
+            "Incorrect result of (0 << 0) + (1 << 0)\nGot 0b10 << 0, expected 0b01 << 0"
+        );
+    }
+}
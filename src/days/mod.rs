@@ -0,0 +1,174 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+
+#[allow(dead_code)]
+mod template;
+
+use crate::solution::{animate, draw, run, Day};
+
+// Every solved day, in calendar order
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        run: run::<day01::Day01>,
+        draw: draw::<day01::Day01>,
+        animate: animate::<day01::Day01>,
+    },
+    Day {
+        day: 2,
+        run: run::<day02::Day02>,
+        draw: draw::<day02::Day02>,
+        animate: animate::<day02::Day02>,
+    },
+    Day {
+        day: 3,
+        run: run::<day03::Day03>,
+        draw: draw::<day03::Day03>,
+        animate: animate::<day03::Day03>,
+    },
+    Day {
+        day: 4,
+        run: run::<day04::Day04>,
+        draw: draw::<day04::Day04>,
+        animate: animate::<day04::Day04>,
+    },
+    Day {
+        day: 5,
+        run: run::<day05::Day05>,
+        draw: draw::<day05::Day05>,
+        animate: animate::<day05::Day05>,
+    },
+    Day {
+        day: 6,
+        run: run::<day06::Day06>,
+        draw: draw::<day06::Day06>,
+        animate: animate::<day06::Day06>,
+    },
+    Day {
+        day: 7,
+        run: run::<day07::Day07>,
+        draw: draw::<day07::Day07>,
+        animate: animate::<day07::Day07>,
+    },
+    Day {
+        day: 8,
+        run: run::<day08::Day08>,
+        draw: draw::<day08::Day08>,
+        animate: animate::<day08::Day08>,
+    },
+    Day {
+        day: 9,
+        run: run::<day09::Day09>,
+        draw: draw::<day09::Day09>,
+        animate: animate::<day09::Day09>,
+    },
+    Day {
+        day: 10,
+        run: run::<day10::Day10>,
+        draw: draw::<day10::Day10>,
+        animate: animate::<day10::Day10>,
+    },
+    Day {
+        day: 11,
+        run: run::<day11::Day11>,
+        draw: draw::<day11::Day11>,
+        animate: animate::<day11::Day11>,
+    },
+    Day {
+        day: 12,
+        run: run::<day12::Day12>,
+        draw: draw::<day12::Day12>,
+        animate: animate::<day12::Day12>,
+    },
+    Day {
+        day: 14,
+        run: run::<day14::Day14>,
+        draw: draw::<day14::Day14>,
+        animate: animate::<day14::Day14>,
+    },
+    Day {
+        day: 15,
+        run: run::<day15::Day15>,
+        draw: draw::<day15::Day15>,
+        animate: animate::<day15::Day15>,
+    },
+    Day {
+        day: 16,
+        run: run::<day16::Day16>,
+        draw: draw::<day16::Day16>,
+        animate: animate::<day16::Day16>,
+    },
+    Day {
+        day: 17,
+        run: run::<day17::Day17>,
+        draw: draw::<day17::Day17>,
+        animate: animate::<day17::Day17>,
+    },
+    Day {
+        day: 18,
+        run: run::<day18::Day18>,
+        draw: draw::<day18::Day18>,
+        animate: animate::<day18::Day18>,
+    },
+    Day {
+        day: 19,
+        run: run::<day19::Day19>,
+        draw: draw::<day19::Day19>,
+        animate: animate::<day19::Day19>,
+    },
+    Day {
+        day: 20,
+        run: run::<day20::Day20>,
+        draw: draw::<day20::Day20>,
+        animate: animate::<day20::Day20>,
+    },
+    Day {
+        day: 21,
+        run: run::<day21::Day21>,
+        draw: draw::<day21::Day21>,
+        animate: animate::<day21::Day21>,
+    },
+    Day {
+        day: 22,
+        run: run::<day22::Day22>,
+        draw: draw::<day22::Day22>,
+        animate: animate::<day22::Day22>,
+    },
+    Day {
+        day: 23,
+        run: run::<day23::Day23>,
+        draw: draw::<day23::Day23>,
+        animate: animate::<day23::Day23>,
+    },
+    Day {
+        day: 24,
+        run: run::<day24::Day24>,
+        draw: draw::<day24::Day24>,
+        animate: animate::<day24::Day24>,
+    },
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,26 @@
+// Starting point for a new day: copy to dayDD.rs, then register it in DAYS in mod.rs
+
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::Solution;
+
+pub struct DayDD;
+
+impl Solution for DayDD {
+    type Input<'a> = Vec<&'a str>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(input: &'a str, _: &Params) -> Result<Self::Input<'a>, AocError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(lines: &Self::Input<'_>) -> Result<usize, AocError> {
+        // BODY
+        Ok(lines.len())
+    }
+
+    fn part2(_: &Self::Input<'_>) -> Result<usize, AocError> {
+        Err(AocError::Unsolved)
+    }
+}
@@ -1,221 +1,1044 @@
-extern crate alloc;
-
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::ops::Add;
 use std::vec::Vec;
 
 use rayon::prelude::*;
 
-use nonempty::{nonempty, NonEmpty};
+use nonempty::NonEmpty;
+use num::{CheckedAdd, Zero};
+
+use crate::grid::Metric;
+
+// Anything that can be used as the cost of an edge: costs are only ever added and compared, and
+// adding them is checked so that an overflowing path cost is caught instead of wrapping around
+pub trait Cost: Ord + Copy + Zero + CheckedAdd {}
+
+impl<C: Ord + Copy + Zero + CheckedAdd> Cost for C {}
+
+fn add<C: Cost>(a: C, b: C) -> C {
+    a.checked_add(&b).expect("Path cost overflowed")
+}
+
+// A cost compared lexicographically, e.g. fewest turns first and then fewest steps. Tuples can't
+// be used directly as they don't implement the num traits.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug, Default)]
+pub struct Lexicographic<A, B>(pub A, pub B);
+
+impl<A: Add<Output = A>, B: Add<Output = B>> Add for Lexicographic<A, B> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Lexicographic(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl<A: Zero, B: Zero> Zero for Lexicographic<A, B> {
+    fn zero() -> Self {
+        Lexicographic(A::zero(), B::zero())
+    }
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<A: CheckedAdd, B: CheckedAdd> CheckedAdd for Lexicographic<A, B> {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Lexicographic(
+            self.0.checked_add(&other.0)?,
+            self.1.checked_add(&other.1)?,
+        ))
+    }
+}
 
-type DijkstraCost = usize;
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-pub struct DijkstraNode<T: Hash + Eq + PartialEq + Copy + Clone> {
+pub struct DijkstraNode<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost = usize> {
     pub pos: T,
-    pub dist: usize,
+    pub dist: C,
 }
 
-impl<T: Hash + Eq + PartialEq + Copy + Clone> Ord for DijkstraNode<T> {
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> Ord for DijkstraNode<T, C> {
     fn cmp(&self, other: &Self) -> Ordering {
         other.dist.cmp(&self.dist)
     }
 }
-impl<T: Hash + Eq + PartialEq + Copy + Clone> PartialOrd for DijkstraNode<T> {
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> PartialOrd for DijkstraNode<T, C> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.dist.cmp(&self.dist))
+        Some(self.cmp(other))
     }
 }
 
 #[derive(Eq, PartialEq, Clone, Hash, Debug)]
-pub struct DijkstraPath<T: Hash + Eq + PartialEq + Copy + Clone> {
-    pub path: NonEmpty<DijkstraNode<T>>,
+pub struct DijkstraPath<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost = usize> {
+    pub path: NonEmpty<DijkstraNode<T, C>>,
 }
 
-impl<T: Hash + Eq + PartialEq + Copy + Clone> DijkstraPath<T> {
-    pub fn dist(&self) -> DijkstraCost {
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> DijkstraPath<T, C> {
+    pub fn dist(&self) -> C {
         self.path.last().dist
     }
 }
 
-impl<T: Hash + Eq + PartialEq + Copy + Clone> Ord for DijkstraPath<T> {
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> Ord for DijkstraPath<T, C> {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.path.last().cmp(&other.path.last())
+        self.path.last().cmp(other.path.last())
     }
 }
 
-impl<T: Hash + Eq + PartialEq + Copy + Clone> PartialOrd for DijkstraPath<T> {
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> PartialOrd for DijkstraPath<T, C> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.path.last().partial_cmp(&other.path.last())
+        Some(self.cmp(other))
+    }
+}
+
+// The result of a search: the best distance found to every discovered node and the node it was
+// reached from. Paths are only rebuilt, by following the predecessors back to a start, for the
+// nodes that are asked about.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost = usize> {
+    pub starts: Vec<T>,
+    pub(crate) dists: HashMap<T, C>,
+    pub(crate) preds: HashMap<T, T>,
+    pub(crate) end: Option<T>,
+}
+
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> ShortestPaths<T, C> {
+    pub(crate) fn new(starts: &[T]) -> Self {
+        ShortestPaths {
+            starts: starts.to_vec(),
+            dists: starts.iter().map(|s| (*s, C::zero())).collect(),
+            preds: HashMap::new(),
+            end: None,
+        }
+    }
+
+    // The end node the search stopped at, if one was reached
+    pub fn end(&self) -> Option<T> {
+        self.end
+    }
+
+    pub fn contains_key(&self, node: &T) -> bool {
+        self.dists.contains_key(node)
+    }
+
+    pub fn dist(&self, node: &T) -> Option<C> {
+        self.dists.get(node).copied()
+    }
+
+    pub fn predecessor(&self, node: &T) -> Option<&T> {
+        self.preds.get(node)
+    }
+
+    // The path to node from the nearest start
+    pub fn get(&self, node: &T) -> Option<DijkstraPath<T, C>> {
+        let mut rev_path = vec![DijkstraNode {
+            pos: *node,
+            dist: self.dist(node)?,
+        }];
+        let mut cur = node;
+        while let Some(p) = self.preds.get(cur) {
+            rev_path.push(DijkstraNode {
+                pos: *p,
+                dist: self.dists[p],
+            });
+            cur = p;
+        }
+        rev_path.reverse();
+        Some(DijkstraPath {
+            path: NonEmpty::from_vec(rev_path).unwrap(),
+        })
+    }
+
+    // Every discovered node with the best distance found to it
+    pub fn dists(&self) -> impl Iterator<Item = (&T, &C)> {
+        self.dists.iter()
     }
 }
 
 // Dijkstra for shortest path between start and end
-pub fn dijkstra<T, NeighborFactory>(
+pub fn dijkstra<T, C, NeighborFactory>(
     start: &T,
     end: &T,
     neighbors: NeighborFactory,
-) -> HashMap<T, DijkstraPath<T>>
+) -> ShortestPaths<T, C>
 where
     T: Copy + Hash + Eq + PartialEq + Clone,
-    NeighborFactory: Fn(&T) -> Vec<(T, DijkstraCost)>,
+    C: Cost,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
 {
     dijkstra_by(start, |p| p == end, neighbors)
 }
 
 // Dijkstra for shortest path between start and first node to satisfy predicate end
-pub fn dijkstra_by<T, EndPredicate, NeighborFactory>(
+pub fn dijkstra_by<T, C, EndPredicate, NeighborFactory>(
     start: &T,
     end: EndPredicate,
     neighbors: NeighborFactory,
-) -> HashMap<T, DijkstraPath<T>>
+) -> ShortestPaths<T, C>
 where
     T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
     EndPredicate: Fn(&T) -> bool,
-    NeighborFactory: Fn(&T) -> Vec<(T, DijkstraCost)>,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
 {
-    let mut queue = BinaryHeap::<DijkstraNode<T>>::new();
-    let mut dists = HashMap::<T, DijkstraPath<T>>::new();
+    astar(start, end, neighbors, |_| C::zero())
+}
 
-    let s = DijkstraNode {
-        pos: *start,
-        dist: 0,
-    };
-    queue.push(s);
-    dists.insert(*start, DijkstraPath { path: nonempty![s] });
+// Dijkstra for shortest path between any of the starts and first node to satisfy predicate end.
+// Every start is seeded at cost zero, so the distance to a node is its distance from the nearest
+// start.
+pub fn dijkstra_multi_by<T, C, EndPredicate, NeighborFactory>(
+    starts: &[T],
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+) -> ShortestPaths<T, C>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
+    EndPredicate: Fn(&T) -> bool,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
+{
+    astar_multi(starts, end, neighbors, |_| C::zero())
+}
+
+// Dijkstra settling every node reachable from the starts, giving the full distance field
+pub fn dijkstra_all<T, C, NeighborFactory>(
+    starts: &[T],
+    neighbors: NeighborFactory,
+) -> ShortestPaths<T, C>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
+{
+    dijkstra_multi_by(starts, |_| false, neighbors)
+}
+
+// An entry in the A* priority queue: a node, ordered by its distance plus its heuristic estimate
+// of the remaining distance. Ties are broken in favour of the node furthest along.
+#[derive(Eq, PartialEq)]
+struct AStarNode<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> {
+    node: DijkstraNode<T, C>,
+    estimate: C,
+}
+
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> Ord for AStarNode<T, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then(self.node.dist.cmp(&other.node.dist))
+    }
+}
+
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> PartialOrd for AStarNode<T, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    while let Some(p) = queue.pop() {
+// A* for shortest path between start and first node to satisfy predicate end.
+// heuristic estimates the remaining distance from a node to the nearest end node. The returned
+// path is a shortest path as long as the heuristic never overestimates; with the zero heuristic
+// this is exactly dijkstra_by.
+pub fn astar<T, C, EndPredicate, NeighborFactory, Heuristic>(
+    start: &T,
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+    heuristic: Heuristic,
+) -> ShortestPaths<T, C>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
+    EndPredicate: Fn(&T) -> bool,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
+    Heuristic: Fn(&T) -> C,
+{
+    astar_multi(std::slice::from_ref(start), end, neighbors, heuristic)
+}
+
+// A* for shortest path between any of the starts and first node to satisfy predicate end
+pub fn astar_multi<T, C, EndPredicate, NeighborFactory, Heuristic>(
+    starts: &[T],
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+    heuristic: Heuristic,
+) -> ShortestPaths<T, C>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
+    EndPredicate: Fn(&T) -> bool,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
+    Heuristic: Fn(&T) -> C,
+{
+    let mut queue = BinaryHeap::<AStarNode<T, C>>::new();
+    let mut paths = ShortestPaths::new(starts);
+
+    for start in starts {
+        queue.push(AStarNode {
+            node: DijkstraNode {
+                pos: *start,
+                dist: C::zero(),
+            },
+            estimate: heuristic(start),
+        });
+    }
+
+    while let Some(AStarNode { node: p, .. }) = queue.pop() {
         let DijkstraNode { pos, dist } = p;
+        if paths.dists[&pos] < dist {
+            continue;
+        }
         if end(&pos) {
+            paths.end = Some(pos);
             break;
         }
-        if dists.get(&pos).map(|d| d.dist() < dist).unwrap_or(false) {
-            continue;
-        }
         for (next, distance_to_next) in neighbors(&pos) {
-            let next_node = DijkstraNode {
-                pos: next,
-                dist: dist + distance_to_next,
-            };
-            let cur_path = dists.get(&pos).unwrap();
-            let path = dists.get(&next);
-            if path.map(|pd| next_node.dist < pd.dist()).unwrap_or(true) {
-                let mut new_path = cur_path.clone();
-                new_path.path.push(next_node);
-                queue.push(next_node);
-                dists.insert(next_node.pos, new_path);
+            let next_dist = add(dist, distance_to_next);
+            if paths.dist(&next).map(|d| next_dist < d).unwrap_or(true) {
+                paths.dists.insert(next, next_dist);
+                paths.preds.insert(next, pos);
+                queue.push(AStarNode {
+                    node: DijkstraNode {
+                        pos: next,
+                        dist: next_dist,
+                    },
+                    estimate: add(next_dist, heuristic(&next)),
+                });
             }
         }
     }
 
     // Unreachable target
-    dists
+    paths
+}
+
+// Admissible A* heuristic for grids with orthogonal moves of cost 1
+pub fn manhattan<P: Metric>(goal: P) -> impl Fn(&P) -> usize {
+    move |p| p.manhattan(&goal)
+}
+
+// Admissible A* heuristic for grids that also allow diagonal moves of cost 1
+pub fn chebyshev<P: Metric>(goal: P) -> impl Fn(&P) -> usize {
+    move |p| p.chebyshev(&goal)
 }
 
-// Yen's algorithm for k shortest paths between start and end
-pub fn yen<T, EndPredicate, NeighborFactory>(
+// Yen's algorithm: an iterator over the loopless paths from start to the nodes satisfying end, in
+// order of increasing cost. Each path is only computed when it is asked for, so stop with take
+// for a number of paths or take_while for a maximum cost.
+pub struct KShortestPaths<T, C, EndPredicate, NeighborFactory>
+where
+    T: Hash + Eq + PartialEq + Copy + Clone,
+    C: Cost + Hash,
+{
+    start: T,
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+    // Paths yielded so far
+    found: Vec<DijkstraPath<T, C>>,
+    // Paths found but not yet yielded, and every path ever put there
+    candidates: BinaryHeap<DijkstraPath<T, C>>,
+    seen: HashSet<DijkstraPath<T, C>>,
+    done: bool,
+}
+
+// The k shortest paths between start and end
+pub fn k_shortest_paths<T, C, NeighborFactory>(
     start: &T,
     end: &T,
     neighbors: NeighborFactory,
-    max_k: Option<usize>,
-    max_dist: Option<DijkstraCost>,
-) -> Vec<DijkstraPath<T>>
+) -> KShortestPaths<T, C, impl Fn(&T) -> bool + Sync, NeighborFactory>
 where
     T: Copy + Hash + Eq + PartialEq + Clone + Sync + Send,
-    NeighborFactory: Fn(&T) -> Vec<(T, DijkstraCost)> + Sync,
+    C: Cost + Hash + Sync + Send,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)> + Sync,
 {
-    let mut out: Vec<DijkstraPath<T>> = Vec::new();
-    if let Some(path) = dijkstra(start, &end, &neighbors).get(&end) {
-        out.push(path.clone());
-    } else {
-        return out;
-    }
-    let mut path_heap = BinaryHeap::<DijkstraPath<T>>::new();
-    let mut paths = HashSet::<DijkstraPath<T>>::new();
-
-    for j in 1.. {
-        let last_path = &out[j - 1];
-        let max_n = last_path.path.len() - 1;
-        let new_paths: Vec<_> = (0..max_n)
+    let end = *end;
+    k_shortest_paths_by(start, move |p: &T| *p == end, neighbors)
+}
+
+// The k shortest paths between start and the nodes satisfying predicate end
+pub fn k_shortest_paths_by<T, C, EndPredicate, NeighborFactory>(
+    start: &T,
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+) -> KShortestPaths<T, C, EndPredicate, NeighborFactory>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone + Sync + Send,
+    C: Cost + Hash + Sync + Send,
+    EndPredicate: Fn(&T) -> bool + Sync,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)> + Sync,
+{
+    KShortestPaths {
+        start: *start,
+        end,
+        neighbors,
+        found: Vec::new(),
+        candidates: BinaryHeap::new(),
+        seen: HashSet::new(),
+        done: false,
+    }
+}
+
+impl<T, C, EndPredicate, NeighborFactory> KShortestPaths<T, C, EndPredicate, NeighborFactory>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone + Sync + Send,
+    C: Cost + Hash + Sync + Send,
+    EndPredicate: Fn(&T) -> bool + Sync,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)> + Sync,
+{
+    fn shortest_from(
+        &self,
+        start: &T,
+        neighbors: impl Fn(&T) -> Vec<(T, C)>,
+    ) -> Option<DijkstraPath<T, C>> {
+        let paths = dijkstra_by(start, &self.end, neighbors);
+        paths.get(&paths.end()?)
+    }
+
+    // The candidate paths that deviate from the last path found at each of its nodes in turn,
+    // avoiding the edges already taken from the same root by the paths found so far
+    fn spurs(&self, last_path: &DijkstraPath<T, C>) -> Vec<DijkstraPath<T, C>> {
+        (0..last_path.path.len() - 1)
             .into_par_iter()
             .filter_map(|n| {
-                let root = last_path
-                    .path
+                let root: Vec<_> = last_path.path.iter().take(n + 1).cloned().collect();
+                let removed_edges: HashSet<(T, T)> = self
+                    .found
                     .iter()
-                    .cloned()
-                    .take(n + 1)
-                    .collect::<Vec<_>>();
-                let mut removed_edges = HashSet::<(T, T)>::new();
-                for p in &out {
-                    if *p.path.iter().take(n + 1).cloned().collect::<Vec<_>>() == root {
-                        if let Some(q) = p.path.get(n)
-                            && let Some(w) = p.path.get(n + 1)
-                        {
-                            removed_edges.insert((q.pos, w.pos));
-                        }
-                    }
-                }
-                let removed_nodes: HashSet<T> = root
-                    .iter()
-                    .map(|DijkstraNode { pos, dist: _ }| pos)
-                    .take(n)
-                    .cloned()
+                    .filter(|p| p.path.iter().take(n + 1).eq(root.iter()))
+                    .filter_map(|p| Some((p.path.get(n)?.pos, p.path.get(n + 1)?.pos)))
                     .collect();
-
-                let subgraph = for<'a> |p: &'a T| -> Vec<(T, DijkstraCost)> {
-                    neighbors(p)
-                        .iter()
+                let removed_nodes: HashSet<T> = root.iter().take(n).map(|p| p.pos).collect();
+                // Only the edges leaving the spur node are removed, even on undirected graphs: the
+                // edges back into it can't be on a shortest spur path, which starts there
+                let subgraph = |p: &T| -> Vec<(T, C)> {
+                    (self.neighbors)(p)
+                        .into_iter()
                         .filter(|(q, _)| {
-                            !removed_nodes.contains(q)
-                                && !removed_edges.contains(&(*p, *q))
-                                && !removed_edges.contains(&(*q, *p))
+                            !removed_nodes.contains(q) && !removed_edges.contains(&(*p, *q))
                         })
-                        .cloned()
                         .collect()
                 };
-                let spur_node = last_path.path[n].pos;
-
-                if let Some(spur) = dijkstra(&spur_node, &end, subgraph).get(&end) {
-                    let root_dist = root.last().map(|n| n.dist).unwrap_or(0);
-                    let mut total_path = root.clone();
-                    total_path.extend(spur.path.iter().skip(1).map(
-                        |DijkstraNode { pos, dist }| DijkstraNode {
-                            pos: *pos,
-                            dist: dist + root_dist,
-                        },
-                    ));
-                    let total_path = DijkstraPath {
-                        path: NonEmpty::from_vec(total_path).unwrap(),
-                    };
-                    Some(total_path)
-                } else {
-                    None
-                }
+                let spur = self.shortest_from(&last_path.path[n].pos, subgraph)?;
+
+                let root_dist = root[n].dist;
+                let mut total_path = root;
+                total_path.extend(spur.path.iter().skip(1).map(|node| DijkstraNode {
+                    pos: node.pos,
+                    dist: add(node.dist, root_dist),
+                }));
+                Some(DijkstraPath {
+                    path: NonEmpty::from_vec(total_path).unwrap(),
+                })
             })
+            .collect()
+    }
+}
+
+impl<T, C, EndPredicate, NeighborFactory> Iterator
+    for KShortestPaths<T, C, EndPredicate, NeighborFactory>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone + Sync + Send,
+    C: Cost + Hash + Sync + Send,
+    EndPredicate: Fn(&T) -> bool + Sync,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)> + Sync,
+{
+    type Item = DijkstraPath<T, C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = match self.found.last() {
+            None => self.shortest_from(&self.start, &self.neighbors),
+            Some(last_path) => {
+                for p in self.spurs(last_path) {
+                    if self.seen.insert(p.clone()) {
+                        self.candidates.push(p);
+                    }
+                }
+                self.candidates.pop()
+            }
+        };
+        match next {
+            Some(path) => {
+                self.found.push(path.clone());
+                Some(path)
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+// The shortest paths DAG found by Dijkstra's algorithm: the distance from the start to every
+// settled node, together with *every* predecessor through which that distance is attained.
+// Any path from the start that only follows predecessor links backwards is a shortest path.
+// Nodes the search discovered but stopped before settling have no distance or paths, as a shorter
+// path to them may not have been found yet.
+#[derive(Clone, Debug)]
+pub struct DijkstraDag<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost = usize> {
+    pub start: T,
+    dists: HashMap<T, C>,
+    preds: HashMap<T, Vec<T>>,
+    // Nodes in the order they were settled, which is a topological order of the DAG
+    order: Vec<T>,
+    settled: HashSet<T>,
+    // Nodes satisfying the end predicate that were reached at the optimal distance
+    ends: Vec<T>,
+}
+
+impl<T: Hash + Eq + PartialEq + Copy + Clone, C: Cost> DijkstraDag<T, C> {
+    pub fn dist(&self, node: &T) -> Option<C> {
+        self.dists
+            .get(node)
+            .copied()
+            .filter(|_| self.settled.contains(node))
+    }
+
+    pub fn predecessors(&self, node: &T) -> &[T] {
+        self.preds.get(node).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    // All end nodes reached at the optimal distance; several if there are ties
+    pub fn ends(&self) -> &[T] {
+        &self.ends
+    }
+
+    // Distance to the nearest end node, if any was reached
+    pub fn optimal_dist(&self) -> Option<C> {
+        self.ends.first().and_then(|e| self.dist(e))
+    }
+
+    fn count_paths_to(&self, targets: &[T]) -> usize {
+        let mut counts = HashMap::<T, usize>::new();
+        counts.insert(self.start, 1);
+        for node in self.order.iter().skip(1) {
+            let n = self
+                .predecessors(node)
+                .iter()
+                .map(|p| counts.get(p).copied().unwrap_or(0))
+                .fold(0, usize::saturating_add);
+            counts.insert(*node, n);
+        }
+        targets
+            .iter()
+            .map(|t| counts.get(t).copied().unwrap_or(0))
+            .fold(0, usize::saturating_add)
+    }
+
+    // Number of distinct shortest paths from the start to node, saturating at usize::MAX
+    pub fn count_paths(&self, node: &T) -> usize {
+        self.count_paths_to(std::slice::from_ref(node))
+    }
+
+    // Number of distinct shortest paths from the start to any optimal end node
+    pub fn count_optimal_paths(&self) -> usize {
+        self.count_paths_to(&self.ends)
+    }
+
+    // Walk the predecessor links backwards from targets, collecting every node and edge seen
+    fn backtrack(&self, targets: &[T]) -> (HashSet<T>, HashSet<(T, T)>) {
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
+        let mut stack: Vec<T> = targets
+            .iter()
+            .filter(|t| self.settled.contains(t))
+            .copied()
             .collect();
-        for p in new_paths {
-            if !paths.contains(&p) {
-                paths.insert(p.clone());
-                path_heap.push(p);
+        while let Some(node) = stack.pop() {
+            if !nodes.insert(node) {
+                continue;
+            }
+            for p in self.predecessors(&node) {
+                edges.insert((*p, node));
+                stack.push(*p);
             }
         }
-        if let Some(path) = path_heap.pop() {
-            if let Some(d) = max_dist
-                && path.dist() > d
-            {
-                break;
+        (nodes, edges)
+    }
+
+    // Every node lying on some shortest path from the start to node
+    pub fn nodes_on_paths(&self, node: &T) -> HashSet<T> {
+        self.backtrack(std::slice::from_ref(node)).0
+    }
+
+    // Every node lying on some shortest path from the start to an optimal end node
+    pub fn nodes_on_optimal_paths(&self) -> HashSet<T> {
+        self.backtrack(&self.ends).0
+    }
+
+    // Every edge (from, to) lying on some shortest path from the start to node
+    pub fn edges_on_paths(&self, node: &T) -> HashSet<(T, T)> {
+        self.backtrack(std::slice::from_ref(node)).1
+    }
+
+    // Every edge (from, to) lying on some shortest path from the start to an optimal end node
+    pub fn edges_on_optimal_paths(&self) -> HashSet<(T, T)> {
+        self.backtrack(&self.ends).1
+    }
+
+    fn paths_to(&self, targets: &[T]) -> Vec<DijkstraPath<T, C>> {
+        // Depth first search backwards from the targets, keeping the partial path (in reverse) on
+        // a stack alongside the index of the next predecessor to try at each step
+        let mut out = Vec::new();
+        for &target in targets.iter().filter(|t| self.settled.contains(t)) {
+            let mut rev_path = vec![target];
+            let mut next_pred = vec![0];
+            while let (Some(&node), Some(&i)) = (rev_path.last(), next_pred.last()) {
+                if node == self.start {
+                    let path = rev_path
+                        .iter()
+                        .rev()
+                        .map(|&pos| DijkstraNode {
+                            pos,
+                            dist: self.dists[&pos],
+                        })
+                        .collect();
+                    out.push(DijkstraPath {
+                        path: NonEmpty::from_vec(path).unwrap(),
+                    });
+                }
+                match self.predecessors(&node).get(i) {
+                    Some(&p) if node != self.start => {
+                        *next_pred.last_mut().unwrap() += 1;
+                        rev_path.push(p);
+                        next_pred.push(0);
+                    }
+                    _ => {
+                        rev_path.pop();
+                        next_pred.pop();
+                    }
+                }
             }
-            out.push(path);
-        } else {
-            break;
         }
-        if let Some(k) = max_k
-            && k == j + 1
-        {
+        out
+    }
+
+    // Every shortest path from the start to node. There can be exponentially many of them; use
+    // count_paths first if in doubt.
+    pub fn paths(&self, node: &T) -> Vec<DijkstraPath<T, C>> {
+        self.paths_to(std::slice::from_ref(node))
+    }
+
+    // Every shortest path from the start to an optimal end node
+    pub fn optimal_paths(&self) -> Vec<DijkstraPath<T, C>> {
+        self.paths_to(&self.ends)
+    }
+}
+
+// Dijkstra recording every shortest path between start and end
+pub fn dijkstra_dag<T, C, NeighborFactory>(
+    start: &T,
+    end: &T,
+    neighbors: NeighborFactory,
+) -> DijkstraDag<T, C>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
+{
+    dijkstra_dag_by(start, |p| p == end, neighbors)
+}
+
+// Dijkstra recording every shortest path between start and the nodes satisfying predicate end.
+// The search carries on until every end node tied for the shortest distance has been settled.
+pub fn dijkstra_dag_by<T, C, EndPredicate, NeighborFactory>(
+    start: &T,
+    end: EndPredicate,
+    neighbors: NeighborFactory,
+) -> DijkstraDag<T, C>
+where
+    T: Copy + Hash + Eq + PartialEq + Clone,
+    C: Cost,
+    EndPredicate: Fn(&T) -> bool,
+    NeighborFactory: Fn(&T) -> Vec<(T, C)>,
+{
+    let mut queue = BinaryHeap::<DijkstraNode<T, C>>::new();
+    let mut dag = DijkstraDag {
+        start: *start,
+        dists: HashMap::from([(*start, C::zero())]),
+        preds: HashMap::new(),
+        order: Vec::new(),
+        settled: HashSet::new(),
+        ends: Vec::new(),
+    };
+
+    queue.push(DijkstraNode {
+        pos: *start,
+        dist: C::zero(),
+    });
+
+    while let Some(DijkstraNode { pos, dist }) = queue.pop() {
+        if dag.optimal_dist().is_some_and(|best| dist > best) {
             break;
         }
+        if dag.dists[&pos] < dist || !dag.settled.insert(pos) {
+            continue;
+        }
+        dag.order.push(pos);
+        if end(&pos) {
+            dag.ends.push(pos);
+            continue;
+        }
+        for (next, distance_to_next) in neighbors(&pos) {
+            let next_dist = add(dist, distance_to_next);
+            match dag.dists.get(&next) {
+                Some(&d) if next_dist > d => {}
+                Some(&d) if next_dist == d => {
+                    // A settled node can only be tied through a zero-cost edge; taking it would
+                    // break the topological order of the DAG, and possibly make it cyclic
+                    if !dag.settled.contains(&next) {
+                        dag.preds.entry(next).or_default().push(pos);
+                    }
+                }
+                _ => {
+                    dag.dists.insert(next, next_dist);
+                    dag.preds.insert(next, vec![pos]);
+                    queue.push(DijkstraNode {
+                        pos: next,
+                        dist: next_dist,
+                    });
+                }
+            }
+        }
     }
 
-    out
+    dag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{step, CardinalDirection, Grid, OrdinalDirection, UPoint};
+
+    // A small directed graph given as (from, to, cost) edges
+    fn graph<C: Copy>(edges: &[(char, char, C)]) -> impl Fn(&char) -> Vec<(char, C)> + '_ {
+        move |p| {
+            edges
+                .iter()
+                .filter(|(a, _, _)| a == p)
+                .map(|&(_, b, c)| (b, c))
+                .collect()
+        }
+    }
+
+    fn positions<T: Hash + Eq + Copy, C: Cost>(path: &DijkstraPath<T, C>) -> Vec<T> {
+        path.path.iter().map(|n| n.pos).collect()
+    }
+
+    #[test]
+    fn dag_ties() {
+        // Two routes of cost 2 from a to d, and a third through e that costs 3
+        let edges = [
+            ('a', 'b', 1),
+            ('a', 'c', 1),
+            ('b', 'd', 1),
+            ('c', 'd', 1),
+            ('a', 'e', 2),
+            ('e', 'd', 1),
+        ];
+        let dag = dijkstra_dag(&'a', &'d', graph(&edges));
+        assert_eq!(dag.optimal_dist(), Some(2));
+        assert_eq!(dag.ends(), &['d']);
+        let mut preds = dag.predecessors(&'d').to_vec();
+        preds.sort();
+        assert_eq!(preds, vec!['b', 'c']);
+        assert_eq!(dag.count_optimal_paths(), 2);
+        let mut paths: Vec<_> = dag.optimal_paths().iter().map(positions).collect();
+        paths.sort();
+        assert_eq!(paths, vec![vec!['a', 'b', 'd'], vec!['a', 'c', 'd']]);
+        assert_eq!(
+            dag.nodes_on_optimal_paths(),
+            HashSet::from(['a', 'b', 'c', 'd'])
+        );
+        assert!(!dag.edges_on_optimal_paths().contains(&('e', 'd')));
+    }
+
+    #[test]
+    fn dag_zero_cost_edges() {
+        // b and c are tied through a zero-cost edge each way, which mustn't make the DAG cyclic
+        let edges = [
+            ('a', 'b', 1),
+            ('a', 'c', 1),
+            ('b', 'c', 0),
+            ('c', 'b', 0),
+            ('b', 'd', 1),
+            ('c', 'd', 1),
+        ];
+        let dag = dijkstra_dag(&'a', &'d', graph(&edges));
+        assert_eq!(dag.optimal_dist(), Some(2));
+        assert_eq!(dag.dist(&'b'), Some(1));
+        assert_eq!(dag.dist(&'c'), Some(1));
+        // The first of b and c to be settled is also a predecessor of the other
+        assert_eq!(dag.count_paths(&'b') + dag.count_paths(&'c'), 3);
+        assert_eq!(dag.count_optimal_paths(), dag.optimal_paths().len());
+    }
+
+    #[test]
+    fn dag_counts_match_paths() {
+        // A grid of nodes 0..16 where every node links right and down at cost 1, with the links
+        // out of node 5 costing 2 so not every lattice path is a shortest one
+        let neighbors = |&n: &u32| -> Vec<(u32, u32)> {
+            let cost = if n == 5 { 2 } else { 1 };
+            let (x, y) = (n % 4, n / 4);
+            let mut out = Vec::new();
+            if x < 3 {
+                out.push((n + 1, cost));
+            }
+            if y < 3 {
+                out.push((n + 4, cost));
+            }
+            out
+        };
+        let dag = dijkstra_dag(&0, &15, neighbors);
+        assert_eq!(dag.optimal_dist(), Some(6));
+        for n in 0..16 {
+            assert_eq!(dag.count_paths(&n), dag.paths(&n).len(), "node {n}");
+        }
+        // Of the 20 lattice paths to the far corner, the 12 through node 5 are longer
+        assert_eq!(dag.count_optimal_paths(), 8);
+    }
+
+    #[test]
+    fn dag_unsettled_nodes() {
+        // The search stops once d is settled, before reaching e through its costly edge
+        let edges = [('a', 'd', 1), ('a', 'e', 5), ('d', 'e', 1)];
+        let dag = dijkstra_dag(&'a', &'d', graph(&edges));
+        assert_eq!(dag.dist(&'e'), None);
+        assert_eq!(dag.count_paths(&'e'), 0);
+        assert!(dag.paths(&'e').is_empty());
+        assert!(dag.nodes_on_paths(&'e').is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Path cost overflowed")]
+    fn cost_overflow() {
+        let edges = [('a', 'b', 200u8), ('b', 'c', 100u8)];
+        dijkstra(&'a', &'c', graph(&edges));
+    }
+
+    #[test]
+    fn other_cost_types() {
+        let edges = [('a', 'b', 3i32), ('b', 'c', 4), ('a', 'c', 9)];
+        assert_eq!(dijkstra(&'a', &'c', graph(&edges)).dist(&'c'), Some(7));
+        let edges = [('a', 'b', u64::MAX - 1), ('b', 'c', 1)];
+        assert_eq!(
+            dijkstra(&'a', &'c', graph(&edges)).dist(&'c'),
+            Some(u64::MAX)
+        );
+
+        // Fewest turns first, then fewest steps: the long way round a has one turn fewer
+        let edges = [
+            ('s', 'a', Lexicographic(0u32, 10u32)),
+            ('a', 'e', Lexicographic(1, 10)),
+            ('s', 'b', Lexicographic(1, 1)),
+            ('b', 'c', Lexicographic(1, 1)),
+            ('c', 'e', Lexicographic(0, 1)),
+        ];
+        let paths = dijkstra(&'s', &'e', graph(&edges));
+        assert_eq!(paths.dist(&'e'), Some(Lexicographic(1, 20)));
+        assert_eq!(positions(&paths.get(&'e').unwrap()), vec!['s', 'a', 'e']);
+        assert_eq!(
+            Lexicographic(u32::MAX, 0).checked_add(&Lexicographic(0, 1)),
+            Some(Lexicographic(u32::MAX, 1))
+        );
+        assert_eq!(
+            Lexicographic(0, u32::MAX).checked_add(&Lexicographic(0, 1)),
+            None
+        );
+    }
+
+    const MAZE: &str = "\
+.....#....
+.###.#.##.
+.#...#..#.
+.#.####.#.
+.#......#.
+.######.#.
+........#.
+";
+
+    // The open cells next to p, orthogonally or also diagonally, at cost 1
+    fn open_neighbors(
+        maze: &Grid<char>,
+        directions: &[OrdinalDirection],
+        p: &UPoint,
+    ) -> Vec<(UPoint, usize)> {
+        directions
+            .iter()
+            .filter_map(|&d| step(*p, d))
+            .filter(|&q| maze.get(q) == Some(&'.'))
+            .map(|q| (q, 1))
+            .collect()
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let maze: Grid<char> = MAZE.parse().unwrap();
+        let orthogonal = CardinalDirection::ALL.map(OrdinalDirection::from);
+        let start = UPoint::new(0, 0);
+        for goal in maze.positions(|&c| c == '.') {
+            let dists = [
+                dijkstra(&start, &goal, |p| open_neighbors(&maze, &orthogonal, p)),
+                astar(
+                    &start,
+                    |p| *p == goal,
+                    |p| open_neighbors(&maze, &orthogonal, p),
+                    |_| 0,
+                ),
+                astar(
+                    &start,
+                    |p| *p == goal,
+                    |p| open_neighbors(&maze, &orthogonal, p),
+                    manhattan(goal),
+                ),
+                astar(
+                    &start,
+                    |p| *p == goal,
+                    |p| open_neighbors(&maze, &OrdinalDirection::ALL, p),
+                    chebyshev(goal),
+                ),
+            ]
+            .map(|paths| paths.end().and_then(|e| paths.dist(&e)));
+            let diagonal = dijkstra(&start, &goal, |p| {
+                open_neighbors(&maze, &OrdinalDirection::ALL, p)
+            });
+            assert_eq!(dists[0], dists[1], "goal {goal}");
+            assert_eq!(dists[0], dists[2], "goal {goal}");
+            assert_eq!(diagonal.dist(&goal), dists[3], "goal {goal}");
+        }
+        // The far side of the wall is only reachable the long way round
+        let goal = UPoint::new(6, 0);
+        let paths = astar(
+            &start,
+            |p| *p == goal,
+            |p| open_neighbors(&maze, &orthogonal, p),
+            manhattan(goal),
+        );
+        assert_eq!(paths.dist(&goal), Some(20));
+        assert_eq!(paths.get(&goal).unwrap().path.len(), 21);
+    }
+
+    #[test]
+    fn nearest_source() {
+        // Sources at 0 and 10 on a line of nodes 0..=10, one step apart
+        let line = |&n: &u32| -> Vec<(u32, u32)> {
+            [n.checked_sub(1), Some(n + 1).filter(|&m| m <= 10)]
+                .into_iter()
+                .flatten()
+                .map(|m| (m, 1))
+                .collect()
+        };
+        let field = dijkstra_all(&[0, 10], line);
+        assert_eq!(field.dists().count(), 11);
+        for n in 0..=10 {
+            assert_eq!(field.dist(&n), Some(n.min(10 - n)), "node {n}");
+            let source = field.get(&n).unwrap().path.first().pos;
+            if n != 5 {
+                assert_eq!(source, if n < 5 { 0 } else { 10 }, "node {n}");
+            }
+        }
+        let paths = dijkstra_multi_by(&[0, 10], |&n| n == 7, line);
+        assert_eq!(paths.end(), Some(7));
+        assert_eq!(positions(&paths.get(&7).unwrap()), vec![10, 9, 8, 7]);
+    }
+
+    #[test]
+    fn stale_entry_at_goal() {
+        // g is first queued at cost 10 straight from a, then more cheaply through b and c
+        let edges = [('a', 'g', 10), ('b', 'c', 1), ('c', 'g', 1), ('a', 'd', 3)];
+        let paths = astar_multi(&['a', 'b'], |&p| p == 'g', graph(&edges), |_| 0);
+        assert_eq!(paths.end(), Some('g'));
+        assert_eq!(paths.dist(&'g'), Some(2));
+        assert_eq!(positions(&paths.get(&'g').unwrap()), vec!['b', 'c', 'g']);
+    }
+
+    // The example graph of Yen's algorithm, with nodes C to H
+    const YEN: [(char, char, u32); 9] = [
+        ('c', 'd', 3),
+        ('c', 'e', 2),
+        ('d', 'f', 4),
+        ('e', 'd', 1),
+        ('e', 'f', 2),
+        ('e', 'g', 3),
+        ('f', 'g', 2),
+        ('f', 'h', 1),
+        ('g', 'h', 2),
+    ];
+
+    #[test]
+    fn k_shortest_in_order() {
+        let paths: Vec<_> = k_shortest_paths(&'c', &'h', graph(&YEN)).collect();
+        let found: Vec<_> = paths.iter().map(|p| (p.dist(), positions(p))).collect();
+        assert_eq!(found[0], (5, vec!['c', 'e', 'f', 'h']));
+        assert_eq!(found[1], (7, vec!['c', 'e', 'g', 'h']));
+        assert_eq!(found[2].0, 8);
+        assert!(found.windows(2).all(|w| w[0].0 <= w[1].0));
+        // Every loopless path from c to h, each found once
+        assert_eq!(found.len(), 7);
+        assert_eq!(found.iter().map(|p| &p.1).collect::<HashSet<_>>().len(), 7);
+    }
+
+    #[test]
+    fn k_shortest_loopless() {
+        // Both ways along every edge, so the paths could go round in circles
+        let undirected: Vec<_> = YEN
+            .iter()
+            .flat_map(|&(a, b, c)| [(a, b, c), (b, a, c)])
+            .collect();
+        let mut last = 0;
+        for path in k_shortest_paths(&'c', &'h', graph(&undirected)).take(20) {
+            let nodes = positions(&path);
+            assert_eq!(nodes.iter().collect::<HashSet<_>>().len(), nodes.len());
+            assert!(path.dist() >= last);
+            last = path.dist();
+        }
+    }
+
+    #[test]
+    fn k_shortest_stops_early() {
+        // Steps of one or two nodes along a line, with the longer steps cheaper per node: each of
+        // the paths to 6 costs 12 less the number of long steps
+        let line = |&n: &u32| -> Vec<(u32, u32)> {
+            [(n + 1, 2), (n + 2, 3)]
+                .into_iter()
+                .filter(|&(m, _)| m <= 6)
+                .collect()
+        };
+        let all: Vec<_> = k_shortest_paths(&0, &6, line).map(|p| p.dist()).collect();
+        assert_eq!(all, [9, 10, 10, 10, 10, 10, 10, 11, 11, 11, 11, 11, 12]);
+        let first: Vec<_> = k_shortest_paths(&0, &6, line).take(2).collect();
+        assert_eq!(first.iter().map(|p| p.dist()).collect::<Vec<_>>(), [9, 10]);
+        let cheap = k_shortest_paths(&0, &6, line)
+            .take_while(|p| p.dist() <= 10)
+            .count();
+        assert_eq!(cheap, 7);
+    }
+
+    #[test]
+    fn k_shortest_to_any_goal() {
+        let mut paths: Vec<_> = k_shortest_paths_by(&'c', |&p| p == 'g' || p == 'h', graph(&YEN))
+            .take(3)
+            .map(|p| (p.dist(), positions(&p)))
+            .collect();
+        // The two paths tied for the shortest can come in either order
+        paths[..2].sort();
+        assert_eq!(
+            paths,
+            [
+                (5, vec!['c', 'e', 'f', 'h']),
+                (5, vec!['c', 'e', 'g']),
+                (6, vec!['c', 'e', 'f', 'g'])
+            ]
+        );
+        assert_eq!(
+            k_shortest_paths_by(&'c', |&p| p == 'x', graph(&YEN)).next(),
+            None
+        );
+    }
 }
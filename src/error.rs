@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+use crate::grid::{ParseDirectionError, ParseGridError};
+use crate::parse::ParseError;
+
+// Everything that can go wrong solving a puzzle or running the solutions
+#[derive(Debug)]
+pub enum AocError {
+    // The input doesn't follow the puzzle's format, at a known line and column
+    Parse(ParseError),
+    // A grid input has an unexpected cell or ragged rows
+    Grid(ParseGridError),
+    // The input doesn't follow the puzzle's format, somewhere a parser can't point at
+    InvalidInput(String),
+    // The input is well-formed but lacks something the puzzle needs, such as a maze's start
+    MissingMarker(&'static str),
+    // There is no way to get to the goal
+    Unreachable(&'static str),
+    // A puzzle parameter is unknown or has a value of the wrong type
+    InvalidParam(String),
+    // A simulated machine got into a state it can't continue from
+    InvalidState(String),
+    // The puzzle has no answer for this input
+    NoSolution(String),
+    // The part hasn't been solved yet
+    Unsolved,
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+impl AocError {
+    // Read a whole file, recording its path in the error if that fails
+    pub fn read(path: &str) -> Result<String, AocError> {
+        std::fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_string(),
+            source,
+        })
+    }
+
+    pub fn write(path: &str, contents: &str) -> Result<(), AocError> {
+        std::fs::write(path, contents).map_err(|source| AocError::Io {
+            path: path.to_string(),
+            source,
+        })
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse(e) => write!(f, "{e}"),
+            AocError::Grid(e) => write!(f, "Invalid input: {e}"),
+            AocError::InvalidInput(msg) => write!(f, "Invalid input: {msg}"),
+            AocError::MissingMarker(what) => write!(f, "Invalid input: no {what}"),
+            AocError::Unreachable(goal) => write!(f, "No path to {goal}"),
+            AocError::InvalidParam(msg) => write!(f, "Invalid parameter: {msg}"),
+            AocError::InvalidState(msg) => write!(f, "Invalid machine state: {msg}"),
+            AocError::NoSolution(why) => write!(f, "No solution: {why}"),
+            AocError::Unsolved => write!(f, "Not solved yet"),
+            AocError::Io { path, source } => write!(f, "Could not access {path}: {source}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Parse(e) => Some(e),
+            AocError::Grid(e) => Some(e),
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        AocError::Parse(e)
+    }
+}
+
+impl From<ParseGridError> for AocError {
+    fn from(e: ParseGridError) -> Self {
+        AocError::Grid(e)
+    }
+}
+
+impl From<ParseDirectionError> for AocError {
+    fn from(e: ParseDirectionError) -> Self {
+        AocError::InvalidInput(e.to_string())
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> Self {
+        AocError::InvalidInput(e.to_string())
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::AocError;
+use crate::grid::Grid;
+
+// The colour of one pixel
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GRAY: Rgb = [96, 96, 96];
+pub const RED: Rgb = [220, 50, 47];
+pub const GREEN: Rgb = [64, 200, 64];
+pub const YELLOW: Rgb = [240, 200, 40];
+pub const BLUE: Rgb = [38, 139, 210];
+pub const BROWN: Rgb = [150, 100, 50];
+
+// Where the frames go, picked from the extension of the output path
+enum Sink {
+    // One numbered image per frame: frames.ppm becomes frames_00000.ppm, frames_00001.ppm, ...
+    Ppm(String),
+    Png(String),
+    // One animated image, opened once the first frame gives its size
+    Gif {
+        path: String,
+        encoder: Option<gif::Encoder<BufWriter<File>>>,
+    },
+}
+
+// Records the states of a step simulation as images. Each step hands over the grid it is in and how
+// to colour its cells; every nth step is kept, drawn with each cell as a square of pixels, and
+// written out straight away so long simulations don't pile up in memory.
+pub struct Recorder {
+    sink: Sink,
+    every: usize,
+    scale: usize,
+    delay: Duration,
+    steps: usize,
+    frames: usize,
+}
+
+impl Recorder {
+    // Record to path, as numbered frames if it ends in .ppm or .png, or an animation if it ends in
+    // .gif
+    pub fn create(path: &str) -> Result<Self, AocError> {
+        let ext = Path::new(path).extension().and_then(|e| e.to_str());
+        let stem = || path[..path.len() - 4].to_string();
+        let sink = match ext {
+            Some("ppm") => Sink::Ppm(stem()),
+            Some("png") => Sink::Png(stem()),
+            Some("gif") => Sink::Gif {
+                path: path.to_string(),
+                encoder: None,
+            },
+            _ => {
+                return Err(AocError::InvalidInput(format!(
+                    "{path}: frames can only be written as .ppm, .png or .gif"
+                )))
+            }
+        };
+        Ok(Recorder {
+            sink,
+            every: 1,
+            scale: 4,
+            delay: Duration::from_millis(50),
+            steps: 0,
+            frames: 0,
+        })
+    }
+
+    // Keep only every nth step, starting with the first
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    // Draw each cell as a square this many pixels wide
+    pub fn scale(mut self, pixels: usize) -> Self {
+        self.scale = pixels.max(1);
+        self
+    }
+
+    // How long each frame of an animation is shown for, to the nearest 10ms
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    // The number of frames written so far
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    // Hand over the state of the next step, colouring each cell with color
+    pub fn record<T, F: Fn(&T) -> Rgb>(
+        &mut self,
+        grid: &Grid<T>,
+        color: F,
+    ) -> Result<(), AocError> {
+        let step = self.steps;
+        self.steps += 1;
+        if !step.is_multiple_of(self.every) {
+            return Ok(());
+        }
+        let (width, height) = (grid.width() * self.scale, grid.height() * self.scale);
+        let pixels = self.pixels(grid, color);
+        let n = self.frames;
+        match &mut self.sink {
+            Sink::Ppm(stem) => {
+                let path = format!("{stem}_{n:05}.ppm");
+                let mut out = create(&path)?;
+                write!(out, "P6\n{width} {height}\n255\n")
+                    .and_then(|_| out.write_all(&pixels.concat()))
+                    .and_then(|_| out.flush())
+                    .map_err(|e| io_error(&path, e))?;
+            }
+            Sink::Png(stem) => {
+                let path = format!("{stem}_{n:05}.png");
+                let mut encoder = png::Encoder::new(create(&path)?, width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder
+                    .write_header()
+                    .and_then(|mut w| w.write_image_data(&pixels.concat()))
+                    .map_err(|e| io_error(&path, e))?;
+            }
+            Sink::Gif { path, encoder } => {
+                let (w, h) = gif_size(width, height, path)?;
+                let encoder = match encoder {
+                    Some(encoder) => encoder,
+                    None => {
+                        let mut e = gif::Encoder::new(create(path)?, w, h, &[])
+                            .map_err(|e| io_error(path, e))?;
+                        e.set_repeat(gif::Repeat::Infinite)
+                            .map_err(|e| io_error(path, e))?;
+                        encoder.insert(e)
+                    }
+                };
+                let mut frame = gif_frame(w, h, &pixels);
+                frame.delay = (self.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+                encoder.write_frame(&frame).map_err(|e| io_error(path, e))?;
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    // Finish writing, returning the number of frames written
+    pub fn finish(self) -> Result<usize, AocError> {
+        if let Sink::Gif {
+            path,
+            encoder: Some(encoder),
+        } = self.sink
+        {
+            encoder
+                .into_inner()
+                .and_then(|mut out| out.flush())
+                .map_err(|e| io_error(&path, e))?;
+        }
+        Ok(self.frames)
+    }
+
+    // The grid scaled up to pixels, in row-major order
+    fn pixels<T, F: Fn(&T) -> Rgb>(&self, grid: &Grid<T>, color: F) -> Vec<Rgb> {
+        let mut pixels = Vec::with_capacity(grid.width() * grid.height() * self.scale.pow(2));
+        for row in grid.rows() {
+            let row: Vec<Rgb> = row
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(color(cell), self.scale))
+                .collect();
+            for _ in 0..self.scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+        pixels
+    }
+}
+
+fn create(path: &str) -> Result<BufWriter<File>, AocError> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| io_error(path, e))
+}
+
+fn io_error<E: Into<Box<dyn std::error::Error + Send + Sync>>>(path: &str, e: E) -> AocError {
+    AocError::Io {
+        path: path.to_string(),
+        source: std::io::Error::other(e),
+    }
+}
+
+fn gif_size(width: usize, height: usize, path: &str) -> Result<(u16, u16), AocError> {
+    match (u16::try_from(width), u16::try_from(height)) {
+        (Ok(w), Ok(h)) => Ok((w, h)),
+        _ => Err(AocError::InvalidInput(format!(
+            "{path}: {width}x{height} is too large for a GIF frame"
+        ))),
+    }
+}
+
+// Simulations only use a handful of colours, so a frame can nearly always be stored exactly with a
+// palette of its own. Frames with more than 256 colours are quantized.
+fn gif_frame(w: u16, h: u16, pixels: &[Rgb]) -> gif::Frame<'static> {
+    let mut palette = HashMap::<Rgb, u8>::new();
+    let mut indices = Vec::with_capacity(pixels.len());
+    for p in pixels {
+        let next = palette.len();
+        if next > 255 && !palette.contains_key(p) {
+            return gif::Frame::from_rgb_speed(w, h, &pixels.concat(), 10);
+        }
+        indices.push(*palette.entry(*p).or_insert(next as u8));
+    }
+    let mut colors = vec![[0; 3]; palette.len()];
+    for (c, i) in palette {
+        colors[i as usize] = c;
+    }
+    gif::Frame::from_palette_pixels(w, h, indices, colors.concat(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "#.\n.#\n".parse().unwrap()
+    }
+
+    fn color(c: &char) -> Rgb {
+        if *c == '#' {
+            WHITE
+        } else {
+            BLACK
+        }
+    }
+
+    #[test]
+    fn scaled_ppm_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sim.ppm");
+        let mut recorder = Recorder::create(path.to_str().unwrap())
+            .unwrap()
+            .every(2)
+            .scale(2);
+        for _ in 0..3 {
+            recorder.record(&grid(), color).unwrap();
+        }
+        assert_eq!(recorder.finish().unwrap(), 2);
+
+        let ppm = std::fs::read(dir.join("sim_00001.ppm")).unwrap();
+        let (header, pixels) = ppm.split_at(11);
+        assert_eq!(header, b"P6\n4 4\n255\n");
+        let white: Vec<bool> = pixels.chunks(3).map(|p| p == WHITE).collect();
+        let row = |a, b| [a, a, b, b];
+        assert_eq!(
+            white,
+            [row(1, 0), row(1, 0), row(0, 1), row(0, 1)]
+                .concat()
+                .iter()
+                .map(|&x| x == 1)
+                .collect::<Vec<_>>()
+        );
+        assert!(!dir.join("sim_00002.ppm").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exact_gif_palette() {
+        let pixels = [WHITE, BLACK, BLACK, RED];
+        let frame = gif_frame(2, 2, &pixels);
+        assert_eq!(frame.buffer.as_ref(), &[0, 1, 1, 2]);
+        assert_eq!(
+            frame.palette.unwrap(),
+            [WHITE, BLACK, RED].concat().to_vec()
+        );
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!(Recorder::create("frames.bmp").is_err());
+    }
+}
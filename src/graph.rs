@@ -0,0 +1,361 @@
+use std::collections::{btree_set, BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+// A directed graph stored as adjacency lists. Nodes and edges are kept in order, so everything
+// computed from a graph comes out the same on every run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph<N> {
+    edges: BTreeMap<N, BTreeSet<N>>,
+}
+
+// A cycle that stops a graph from being sorted: each node has an edge to the next, and the last
+// has an edge back to the first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError<N>(pub Vec<N>);
+
+impl<N: fmt::Display> fmt::Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for n in &self.0 {
+            write!(f, "{n} -> ")?;
+        }
+        match self.0.first() {
+            Some(n) => write!(f, "{n}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: Ord + Copy> Graph<N> {
+    pub fn new() -> Self {
+        Graph {
+            edges: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, n: N) {
+        self.edges.entry(n).or_default();
+    }
+
+    // Add an edge from a to b, and the two nodes if they are new
+    pub fn add_edge(&mut self, a: N, b: N) {
+        self.edges.entry(a).or_default().insert(b);
+        self.add_node(b);
+    }
+
+    pub fn contains(&self, n: N) -> bool {
+        self.edges.contains_key(&n)
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = N> + '_ {
+        self.edges.keys().copied()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (N, N)> + '_ {
+        self.edges
+            .iter()
+            .flat_map(|(&a, bs)| bs.iter().map(move |&b| (a, b)))
+    }
+
+    // The nodes with an edge from n
+    pub fn successors(&self, n: N) -> impl Iterator<Item = N> + '_ {
+        self.edges.get(&n).into_iter().flatten().copied()
+    }
+
+    // The graph with only the given nodes and the edges between them
+    pub fn induced<I: IntoIterator<Item = N>>(&self, nodes: I) -> Self {
+        let keep: BTreeSet<N> = nodes.into_iter().filter(|&n| self.contains(n)).collect();
+        let edges = keep
+            .iter()
+            .map(|&n| (n, self.successors(n).filter(|m| keep.contains(m)).collect()))
+            .collect();
+        Graph { edges }
+    }
+
+    // Whether every edge between nodes in order goes forwards. Nodes of the graph missing from
+    // order, and nodes in order missing from the graph, are ignored.
+    pub fn is_topological_order(&self, order: &[N]) -> bool {
+        let position: BTreeMap<N, usize> = order.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        self.edges()
+            .all(|(a, b)| match (position.get(&a), position.get(&b)) {
+                (Some(i), Some(j)) => i < j,
+                _ => true,
+            })
+    }
+
+    // Order the nodes so that every edge goes forwards, with Kahn's algorithm: repeatedly take a
+    // node that no remaining node has an edge to. Nodes are taken in the order they become ready,
+    // smallest first among those ready from the start.
+    pub fn topological_sort(&self) -> Result<Vec<N>, CycleError<N>> {
+        let mut in_degree: BTreeMap<N, usize> = self.nodes().map(|n| (n, 0)).collect();
+        for (_, b) in self.edges() {
+            *in_degree.get_mut(&b).unwrap() += 1;
+        }
+        let mut ready: VecDeque<N> = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&n, _)| n)
+            .collect();
+        let mut out = Vec::with_capacity(self.len());
+        while let Some(n) = ready.pop_front() {
+            out.push(n);
+            for m in self.successors(n) {
+                let d = in_degree.get_mut(&m).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    ready.push_back(m);
+                }
+            }
+        }
+        if out.len() == self.len() {
+            return Ok(out);
+        }
+
+        // Every node left over still has an edge to it from another left over node, so walking
+        // those edges backwards from any of them must come round in a cycle
+        let left: BTreeSet<N> = in_degree
+            .into_iter()
+            .filter(|(_, d)| *d > 0)
+            .map(|(n, _)| n)
+            .collect();
+        let mut predecessor = BTreeMap::new();
+        for (a, b) in self.edges() {
+            if left.contains(&a) && left.contains(&b) {
+                predecessor.entry(b).or_insert(a);
+            }
+        }
+        let mut walk = vec![*left.first().unwrap()];
+        let mut seen = BTreeMap::from([(walk[0], 0)]);
+        loop {
+            let prev = predecessor[walk.last().unwrap()];
+            if let Some(&start) = seen.get(&prev) {
+                let mut cycle = walk.split_off(start);
+                cycle.reverse();
+                return Err(CycleError(cycle));
+            }
+            seen.insert(prev, walk.len());
+            walk.push(prev);
+        }
+    }
+
+    // Order the nodes so that every edge goes forwards, by depth first search: a node is finished
+    // once everything it has an edge to is, and the reverse of the finishing order is sorted.
+    // Reaching a node that is still being searched means the path to it is a cycle.
+    pub fn topological_sort_dfs(&self) -> Result<Vec<N>, CycleError<N>> {
+        let mut finished = BTreeSet::new();
+        let mut out = Vec::with_capacity(self.len());
+        for root in self.nodes() {
+            if finished.contains(&root) {
+                continue;
+            }
+            let mut path = vec![(root, self.successors(root))];
+            let mut on_path = BTreeSet::from([root]);
+            while let Some((n, successors)) = path.last_mut() {
+                let n = *n;
+                match successors.find(|m| !finished.contains(m)) {
+                    Some(m) if on_path.contains(&m) => {
+                        let start = path.iter().position(|(p, _)| *p == m).unwrap();
+                        return Err(CycleError(path[start..].iter().map(|(p, _)| *p).collect()));
+                    }
+                    Some(m) => {
+                        on_path.insert(m);
+                        path.push((m, self.successors(m)));
+                    }
+                    None => {
+                        path.pop();
+                        on_path.remove(&n);
+                        finished.insert(n);
+                        out.push(n);
+                    }
+                }
+            }
+        }
+        out.reverse();
+        Ok(out)
+    }
+
+    // The strongly connected components, i.e. the largest sets of nodes that can all reach each
+    // other, found with Tarjan's algorithm. A node on no cycle is a component of its own. Each
+    // component is sorted, and the components come in reverse topological order: no component
+    // has an edge to one after it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: BTreeMap::new(),
+            low: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            search: Vec::new(),
+            components: Vec::new(),
+        };
+        for root in self.nodes() {
+            if !tarjan.index.contains_key(&root) {
+                tarjan.search_from(root);
+            }
+        }
+        tarjan.components
+    }
+}
+
+// The state of Tarjan's algorithm, which searches depth first from each node not yet reached
+struct Tarjan<'g, N> {
+    graph: &'g Graph<N>,
+    // The order each node was reached in, and the earliest reached node still on the stack that it
+    // is known to reach
+    index: BTreeMap<N, usize>,
+    low: BTreeMap<N, usize>,
+    // The nodes reached whose component isn't known yet
+    stack: Vec<N>,
+    on_stack: BTreeSet<N>,
+    // The path of the search, with the edges left to follow from each node on it
+    search: Vec<(N, btree_set::Iter<'g, N>)>,
+    components: Vec<Vec<N>>,
+}
+
+impl<N: Ord + Copy> Tarjan<'_, N> {
+    fn reach(&mut self, n: N) {
+        self.index.insert(n, self.index.len());
+        self.low.insert(n, self.index[&n]);
+        self.stack.push(n);
+        self.on_stack.insert(n);
+        self.search.push((n, self.graph.edges[&n].iter()));
+    }
+
+    fn search_from(&mut self, root: N) {
+        self.reach(root);
+        while let Some((n, successors)) = self.search.last_mut() {
+            let n = *n;
+            match successors.next().copied() {
+                Some(m) if !self.index.contains_key(&m) => self.reach(m),
+                Some(m) => {
+                    if self.on_stack.contains(&m) {
+                        self.low.insert(n, self.low[&n].min(self.index[&m]));
+                    }
+                }
+                None => {
+                    self.search.pop();
+                    if let Some(parent) = self.search.last().map(|(p, _)| *p) {
+                        self.low.insert(parent, self.low[&parent].min(self.low[&n]));
+                    }
+                    // n is the first node of its component to be reached, so the component is
+                    // everything above it on the stack
+                    if self.low[&n] == self.index[&n] {
+                        let start = self.stack.iter().rposition(|&m| m == n).unwrap();
+                        let mut component = self.stack.split_off(start);
+                        for m in &component {
+                            self.on_stack.remove(m);
+                        }
+                        component.sort();
+                        self.components.push(component);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<N: Ord + Copy> Default for Graph<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Ord + Copy> FromIterator<(N, N)> for Graph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut graph = Graph::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Getting dressed: 1 underwear, 2 trousers, 3 belt, 4 shirt, 5 tie, 6 jacket, 7 socks, 8 shoes
+    fn clothes() -> Graph<u32> {
+        let mut g: Graph<u32> = [
+            (1, 2),
+            (1, 8),
+            (2, 3),
+            (2, 8),
+            (4, 3),
+            (4, 5),
+            (5, 6),
+            (3, 6),
+        ]
+        .into_iter()
+        .chain([(7, 8)])
+        .collect();
+        g.add_node(9);
+        g
+    }
+
+    #[test]
+    fn sorts() {
+        let g = clothes();
+        let kahn = g.topological_sort().unwrap();
+        assert_eq!(kahn, vec![1, 4, 7, 9, 2, 5, 3, 8, 6]);
+        let dfs = g.topological_sort_dfs().unwrap();
+        assert_eq!(dfs.len(), 9);
+        assert!(g.is_topological_order(&kahn));
+        assert!(g.is_topological_order(&dfs));
+        // Only edges between the listed nodes count, here 4 -> 3
+        assert!(g.is_topological_order(&[4, 1, 3]));
+        assert!(!g.is_topological_order(&[3, 1, 4]));
+    }
+
+    fn rotate_to_min(cycle: Vec<u32>) -> Vec<u32> {
+        let start = cycle.iter().position(|n| n == cycle.iter().min().unwrap());
+        let (a, b) = cycle.split_at(start.unwrap());
+        [b, a].concat()
+    }
+
+    #[test]
+    fn reports_cycles() {
+        // Shoes before trousers, as well as trousers before shoes
+        let mut g = clothes();
+        g.add_edge(8, 2);
+        let kahn = g.topological_sort().unwrap_err();
+        let dfs = g.topological_sort_dfs().unwrap_err();
+        for cycle in [kahn, dfs] {
+            let n = cycle.0.len();
+            for i in 0..n {
+                assert!(g.successors(cycle.0[i]).any(|m| m == cycle.0[(i + 1) % n]));
+            }
+            assert_eq!(rotate_to_min(cycle.0), vec![2, 8]);
+        }
+        assert_eq!(CycleError(vec![2, 8]).to_string(), "2 -> 8 -> 2");
+
+        let mut loop_ = Graph::new();
+        loop_.add_edge('a', 'a');
+        assert_eq!(loop_.topological_sort(), Err(CycleError(vec!['a'])));
+        assert_eq!(loop_.topological_sort_dfs(), Err(CycleError(vec!['a'])));
+    }
+
+    #[test]
+    fn components() {
+        let mut g = clothes();
+        g.add_edge(8, 2);
+        g.add_edge(6, 4);
+        let sccs = g.strongly_connected_components();
+        assert_eq!(
+            sccs,
+            vec![vec![3, 4, 5, 6], vec![2, 8], vec![1], vec![7], vec![9]]
+        );
+        assert_eq!(clothes().strongly_connected_components().len(), 9);
+        assert_eq!(
+            g.induced([2, 3, 8]).edges().collect::<Vec<_>>(),
+            [(2, 3), (2, 8), (8, 2)]
+        );
+    }
+}
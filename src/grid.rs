@@ -1,5 +1,6 @@
-use coordinates::two_dimensional::Vector2;
-use num::traits::{CheckedAdd, CheckedSub};
+use std::collections::HashMap;
+
+use crate::vector::{Component, Vector2};
 
 pub type UPoint = Vector2<usize>;
 pub type IPoint = Vector2<isize>;
@@ -12,47 +13,170 @@ pub enum CardinalDirection {
     West,
 }
 
-pub fn clockwise(c: CardinalDirection) -> CardinalDirection {
-    match c {
-        CardinalDirection::North => CardinalDirection::East,
-        CardinalDirection::East => CardinalDirection::South,
-        CardinalDirection::South => CardinalDirection::West,
-        CardinalDirection::West => CardinalDirection::North,
+impl CardinalDirection {
+    // Clockwise from north
+    pub const ALL: [CardinalDirection; 4] = [
+        CardinalDirection::North,
+        CardinalDirection::East,
+        CardinalDirection::South,
+        CardinalDirection::West,
+    ];
+
+    // Rotate by the given number of quarter turns; positive is clockwise
+    pub fn rotate(self, quarter_turns: isize) -> Self {
+        Self::ALL[(self as isize + quarter_turns).rem_euclid(4) as usize]
     }
-}
 
-pub fn counterclockwise(c: CardinalDirection) -> CardinalDirection {
-    match c {
-        CardinalDirection::North => CardinalDirection::West,
-        CardinalDirection::East => CardinalDirection::North,
-        CardinalDirection::South => CardinalDirection::East,
-        CardinalDirection::West => CardinalDirection::South,
+    pub fn clockwise(self) -> Self {
+        self.rotate(1)
     }
-}
 
-pub fn step(p: UPoint, c: CardinalDirection) -> Option<UPoint> {
-    match c {
-        CardinalDirection::North => p.checked_sub(&(0, 1).into()),
-        CardinalDirection::South => p.checked_add(&(0, 1).into()),
-        CardinalDirection::East => p.checked_add(&(1, 0).into()),
-        CardinalDirection::West => p.checked_sub(&(1, 0).into()),
+    pub fn counterclockwise(self) -> Self {
+        self.rotate(-1)
+    }
+
+    // The step taken moving one cell in this direction, with y increasing southwards
+    pub fn delta(self) -> IPoint {
+        OrdinalDirection::from(self).delta()
     }
 }
 
 impl std::ops::Neg for CardinalDirection {
     type Output = Self;
     fn neg(self) -> Self {
+        self.rotate(2)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseDirectionError(pub char);
+
+impl std::fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid direction '{}' (expected one of ^>v<)", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+impl TryFrom<char> for CardinalDirection {
+    type Error = ParseDirectionError;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' => Ok(CardinalDirection::North),
+            '>' => Ok(CardinalDirection::East),
+            'v' => Ok(CardinalDirection::South),
+            '<' => Ok(CardinalDirection::West),
+            _ => Err(ParseDirectionError(c)),
+        }
+    }
+}
+
+impl From<CardinalDirection> for char {
+    fn from(c: CardinalDirection) -> char {
+        match c {
+            CardinalDirection::North => '^',
+            CardinalDirection::East => '>',
+            CardinalDirection::South => 'v',
+            CardinalDirection::West => '<',
+        }
+    }
+}
+
+impl std::fmt::Display for CardinalDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+// The eight directions to the cells surrounding a cell, including diagonals
+#[derive(Hash, Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum OrdinalDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl OrdinalDirection {
+    // Clockwise from north
+    pub const ALL: [OrdinalDirection; 8] = [
+        OrdinalDirection::North,
+        OrdinalDirection::NorthEast,
+        OrdinalDirection::East,
+        OrdinalDirection::SouthEast,
+        OrdinalDirection::South,
+        OrdinalDirection::SouthWest,
+        OrdinalDirection::West,
+        OrdinalDirection::NorthWest,
+    ];
+
+    // Rotate by the given number of eighth turns; positive is clockwise
+    pub fn rotate(self, eighth_turns: isize) -> Self {
+        Self::ALL[(self as isize + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    pub fn clockwise(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn counterclockwise(self) -> Self {
+        self.rotate(-1)
+    }
+
+    // The step taken moving one cell in this direction, with y increasing southwards
+    pub fn delta(self) -> IPoint {
+        match self {
+            OrdinalDirection::North => (0, -1),
+            OrdinalDirection::NorthEast => (1, -1),
+            OrdinalDirection::East => (1, 0),
+            OrdinalDirection::SouthEast => (1, 1),
+            OrdinalDirection::South => (0, 1),
+            OrdinalDirection::SouthWest => (-1, 1),
+            OrdinalDirection::West => (-1, 0),
+            OrdinalDirection::NorthWest => (-1, -1),
+        }
+        .into()
+    }
+
+    // The same direction as a CardinalDirection, unless it is diagonal
+    pub fn to_cardinal(self) -> Option<CardinalDirection> {
         match self {
-            CardinalDirection::North => CardinalDirection::South,
-            CardinalDirection::South => CardinalDirection::North,
-            CardinalDirection::East => CardinalDirection::West,
-            CardinalDirection::West => CardinalDirection::East,
+            OrdinalDirection::North => Some(CardinalDirection::North),
+            OrdinalDirection::East => Some(CardinalDirection::East),
+            OrdinalDirection::South => Some(CardinalDirection::South),
+            OrdinalDirection::West => Some(CardinalDirection::West),
+            _ => None,
         }
     }
 }
 
+impl From<CardinalDirection> for OrdinalDirection {
+    fn from(c: CardinalDirection) -> Self {
+        OrdinalDirection::ALL[2 * c as usize]
+    }
+}
+
+impl std::ops::Neg for OrdinalDirection {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.rotate(4)
+    }
+}
+
+// The neighboring point in direction d, unless that would leave the first quadrant
+pub fn step<D: Into<OrdinalDirection>>(p: UPoint, d: D) -> Option<UPoint> {
+    let IPoint { x: dx, y: dy } = d.into().delta();
+    Option::zip(p.x.checked_add_signed(dx), p.y.checked_add_signed(dy)).map(UPoint::from)
+}
+
+// The orthogonal neighbours of p with coordinates of at most w and h
 pub fn neighbors_within_bounds(p: &UPoint, (w, h): (usize, usize)) -> Vec<UPoint> {
-    neighbors_unbounded(&p)
+    neighbors_unbounded(p)
         .iter()
         .cloned()
         .filter(|q| q.x <= w && q.y <= h)
@@ -60,7 +184,7 @@ pub fn neighbors_within_bounds(p: &UPoint, (w, h): (usize, usize)) -> Vec<UPoint
 }
 
 pub fn neighbors_unbounded(p: &UPoint) -> Vec<UPoint> {
-    vec![
+    [
         p.checked_add(&(1, 0).into()),
         p.checked_sub(&(1, 0).into()),
         p.checked_add(&(0, 1).into()),
@@ -79,3 +203,578 @@ pub fn neighbors(p: &IPoint) -> Vec<IPoint> {
         *p + (0isize, -1isize).into(),
     ]
 }
+
+// Distances between grid points
+pub trait Metric {
+    // Number of orthogonal steps from self to other
+    fn manhattan(&self, other: &Self) -> usize;
+    // Number of steps from self to other when diagonal steps are allowed
+    fn chebyshev(&self, other: &Self) -> usize;
+}
+
+impl<T: Component> Metric for Vector2<T> {
+    fn manhattan(&self, other: &Self) -> usize {
+        self.manhattan_distance(other)
+    }
+    fn chebyshev(&self, other: &Self) -> usize {
+        self.chebyshev_distance(other)
+    }
+}
+
+pub fn to_signed(p: UPoint) -> IPoint {
+    IPoint {
+        x: p.x as isize,
+        y: p.y as isize,
+    }
+}
+
+// Anything that can be used to address a cell in a Grid. Signed points are allowed so that
+// callers can step off the edge of the grid and get None back instead of underflowing.
+pub trait GridIndex: Copy {
+    fn to_upoint(self) -> Option<UPoint>;
+}
+
+impl GridIndex for UPoint {
+    fn to_upoint(self) -> Option<UPoint> {
+        Some(self)
+    }
+}
+
+impl GridIndex for IPoint {
+    fn to_upoint(self) -> Option<UPoint> {
+        Some(UPoint {
+            x: self.x.try_into().ok()?,
+            y: self.y.try_into().ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    // The puzzle text did not contain any cells
+    Empty,
+    // A row was not as wide as the first row
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    // The mapping closure rejected a character
+    InvalidCell {
+        pos: UPoint,
+        c: char,
+    },
+}
+
+impl std::fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "Invalid grid: no cells"),
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid grid: row {row} is {found} cells wide, expected {expected}"
+            ),
+            ParseGridError::InvalidCell { pos, c } => {
+                write!(f, "Invalid grid: unexpected character {c:?} at {pos}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+// A dense, rectangular grid of cells stored row by row. Cells are addressed by points whose x
+// coordinate is the column and whose y coordinate is the row, with (0, 0) in the top left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Build a grid by evaluating f at every point
+    pub fn from_fn<F: FnMut(UPoint) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| UPoint { x, y }))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Parse puzzle text into a grid, one line per row, mapping each character to a cell with f.
+    // Parsing stops at the first blank line, so the grid section of a multi-section input can be
+    // passed in as is.
+    pub fn parse<F: FnMut(char) -> Option<T>>(
+        input: &str,
+        mut f: F,
+    ) -> Result<Self, ParseGridError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().take_while(|l| !l.is_empty()).enumerate() {
+            let mut found = 0;
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).ok_or(ParseGridError::InvalidCell {
+                    pos: UPoint { x, y },
+                    c,
+                })?);
+                found += 1;
+            }
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseGridError::Ragged {
+                    row: y,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseGridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // (width, height). Unlike the bounds taken by neighbors_within_bounds, these are one past the
+    // largest coordinates.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn offset<P: GridIndex>(&self, p: P) -> Option<usize> {
+        let UPoint { x, y } = p.to_upoint()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn in_bounds<P: GridIndex>(&self, p: P) -> bool {
+        self.offset(p).is_some()
+    }
+
+    pub fn get<P: GridIndex>(&self, p: P) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<P: GridIndex>(&mut self, p: P) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    // All points of the grid in row-major order
+    pub fn points(&self) -> impl Iterator<Item = UPoint> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| UPoint { x, y }))
+    }
+
+    // All cells of the grid together with their coordinates, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (UPoint, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (UPoint, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    // The cells of each row in turn. A grid with no columns still has its rows, all empty.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        let width = self.width;
+        (0..self.height).map(move |y| &self.cells[y * width..(y + 1) * width])
+    }
+
+    // Coordinates of every cell satisfying pred
+    pub fn positions<'a, F: Fn(&T) -> bool + 'a>(
+        &'a self,
+        pred: F,
+    ) -> impl Iterator<Item = UPoint> + 'a {
+        self.iter().filter(move |(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    // Coordinates of the first cell (in row-major order) satisfying pred
+    pub fn position<F: Fn(&T) -> bool>(&self, pred: F) -> Option<UPoint> {
+        self.positions(pred).next()
+    }
+
+    // Coordinates of the first cell equal to marker, e.g. the 'S' in a maze
+    pub fn find(&self, marker: &T) -> Option<UPoint>
+    where
+        T: PartialEq,
+    {
+        self.position(|c| c == marker)
+    }
+
+    // The orthogonal neighbours of p that lie within the grid
+    pub fn neighbors(&self, p: UPoint) -> Vec<UPoint> {
+        neighbors_unbounded(&p)
+            .into_iter()
+            .filter(|q| self.in_bounds(*q))
+            .collect()
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl std::str::FromStr for Grid<char> {
+    type Err = ParseGridError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Some)
+    }
+}
+
+impl<T, P: GridIndex> std::ops::Index<P> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: P) -> &T {
+        let (w, h) = self.dimensions();
+        self.get(p)
+            .unwrap_or_else(|| panic!("Grid index out of bounds for {w}x{h} grid"))
+    }
+}
+
+impl<T, P: GridIndex> std::ops::IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, p: P) -> &mut T {
+        let (w, h) = self.dimensions();
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("Grid index out of bounds for {w}x{h} grid"))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for c in row {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// An unbounded grid storing only its occupied cells, addressed by signed points. The bounding box
+// of the occupied cells is kept up to date as cells come and go.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<IPoint, T>,
+    // Top left and bottom right corners, inclusive
+    bounds: Option<(IPoint, IPoint)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Keep the cells of a dense grid satisfying pred, e.g. everything but the '.'s
+    pub fn from_grid<F: Fn(&T) -> bool>(grid: &Grid<T>, pred: F) -> Self
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, c)| pred(c))
+            .map(|(p, c)| (to_signed(p), c.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The top left and bottom right occupied corners, inclusive, or None if the grid is empty
+    pub fn bounds(&self) -> Option<(IPoint, IPoint)> {
+        self.bounds
+    }
+
+    // The width and height of the bounding box
+    pub fn dimensions(&self) -> (usize, usize) {
+        match self.bounds {
+            Some((min, max)) => ((max.x - min.x) as usize + 1, (max.y - min.y) as usize + 1),
+            None => (0, 0),
+        }
+    }
+
+    pub fn contains(&self, p: IPoint) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: IPoint) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: IPoint) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    // Occupy p, returning what was there before
+    pub fn insert(&mut self, p: IPoint, value: T) -> Option<T> {
+        self.bounds = Some(grow(self.bounds, p));
+        self.cells.insert(p, value)
+    }
+
+    // Empty p, returning what was there. Removing a cell on the edge of the bounding box costs a
+    // pass over all cells to shrink it.
+    pub fn remove(&mut self, p: IPoint) -> Option<T> {
+        let removed = self.cells.remove(&p)?;
+        let on_edge = |(min, max): (IPoint, IPoint)| {
+            p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y
+        };
+        if self.bounds.is_some_and(on_edge) {
+            self.bounds = bounding_box(self.cells.keys());
+        }
+        Some(removed)
+    }
+
+    // The occupied cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (IPoint, &T)> {
+        self.cells.iter().map(|(p, c)| (*p, c))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = IPoint> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // The occupied orthogonal neighbours of p
+    pub fn neighbors(&self, p: IPoint) -> Vec<IPoint> {
+        neighbors(&p)
+            .into_iter()
+            .filter(|q| self.contains(*q))
+            .collect()
+    }
+
+    // A dense grid covering the bounding box, with fill in the empty cells. The top left corner of
+    // the bounding box becomes (0, 0).
+    pub fn to_grid(&self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some((min, _)) = self.bounds else {
+            return Grid::new(0, 0, fill);
+        };
+        let (w, h) = self.dimensions();
+        Grid::from_fn(w, h, |p| {
+            self.get(to_signed(p) + min)
+                .cloned()
+                .unwrap_or_else(|| fill.clone())
+        })
+    }
+
+    // Draw the bounding box as text, one line per row, with empty for the unoccupied cells
+    pub fn render(&self, empty: char) -> String
+    where
+        T: std::fmt::Display,
+    {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+        let mut out = String::new();
+        for y in min.y..=max.y {
+            if y > min.y {
+                out.push('\n');
+            }
+            for x in min.x..=max.x {
+                match self.get(IPoint { x, y }) {
+                    Some(c) => out.push_str(&c.to_string()),
+                    None => out.push(empty),
+                }
+            }
+        }
+        out
+    }
+}
+
+// The smallest box containing both bounds and p
+fn grow(bounds: Option<(IPoint, IPoint)>, p: IPoint) -> (IPoint, IPoint) {
+    match bounds {
+        Some((min, max)) => (
+            IPoint {
+                x: min.x.min(p.x),
+                y: min.y.min(p.y),
+            },
+            IPoint {
+                x: max.x.max(p.x),
+                y: max.y.max(p.y),
+            },
+        ),
+        None => (p, p),
+    }
+}
+
+fn bounding_box<'a>(points: impl Iterator<Item = &'a IPoint>) -> Option<(IPoint, IPoint)> {
+    points.fold(None, |bounds, &p| Some(grow(bounds, p)))
+}
+
+impl<T> FromIterator<(IPoint, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (IPoint, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(IPoint, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (IPoint, T)>>(&mut self, iter: I) {
+        for (p, c) in iter {
+            self.insert(p, c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_grid() {
+        let grid: Grid<char> = "ab.\n.c#\n\nnot part of the grid\n".parse().unwrap();
+        assert_eq!(grid.dimensions(), (3, 2));
+        assert_eq!(grid.to_string(), "ab.\n.c#");
+        assert_eq!(grid.find(&'c'), Some(UPoint::new(1, 1)));
+
+        assert_eq!(
+            "ab.\n.c\n".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        assert_eq!("\nab\n".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        let digits = Grid::parse("12\n3x\n", |c| c.to_digit(10));
+        assert_eq!(
+            digits,
+            Err(ParseGridError::InvalidCell {
+                pos: UPoint::new(1, 1),
+                c: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn indexing() {
+        let mut grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        assert_eq!(grid[UPoint::new(2, 1)], 12);
+        assert_eq!(grid[IPoint::new(1, 0)], 1);
+        assert_eq!(grid.get(UPoint::new(3, 0)), None);
+        assert_eq!(grid.get(IPoint::new(0, -1)), None);
+        assert!(!grid.in_bounds(UPoint::new(0, 2)));
+        grid[UPoint::new(0, 1)] = 7;
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[0, 1, 2], [7, 11, 12]]);
+        assert_eq!(grid.map(|&v| v % 2 == 0).positions(|&even| even).count(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Grid index out of bounds for 3x2 grid")]
+    fn index_out_of_bounds() {
+        let _ = Grid::new(3, 2, 0)[UPoint::new(1, 2)];
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let grid = Grid::new(3, 2, ());
+        let mut corner = grid.neighbors(UPoint::new(0, 0));
+        corner.sort();
+        assert_eq!(corner, [UPoint::new(0, 1), UPoint::new(1, 0)]);
+        assert_eq!(grid.neighbors(UPoint::new(1, 1)).len(), 3);
+        // Bounds are the largest coordinates, so (2, 2) is a neighbor of (2, 1) here
+        assert_eq!(neighbors_within_bounds(&UPoint::new(2, 1), (2, 2)).len(), 3);
+    }
+
+    #[test]
+    fn empty_grids() {
+        let grid = Grid::new(0, 0, '.');
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+        assert_eq!(Grid::new(0, 2, '.').rows().collect::<Vec<_>>(), [[], []]);
+        assert_eq!(SparseGrid::<char>::new().to_grid('.').to_string(), "");
+    }
+
+    #[test]
+    fn sparse_bounds() {
+        let mut grid: SparseGrid<char> = [((0, 0), 'a'), ((-2, 3), 'b'), ((4, -1), 'c')]
+            .into_iter()
+            .map(|(p, c)| (p.into(), c))
+            .collect();
+        assert_eq!(grid.bounds(), Some(((-2, -1).into(), (4, 3).into())));
+        assert_eq!(grid.dimensions(), (7, 5));
+        grid.remove((4, -1).into());
+        assert_eq!(grid.bounds(), Some(((-2, 0).into(), (0, 3).into())));
+        grid.remove((0, 0).into());
+        grid.remove((-2, 3).into());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn sparse_neighbors() {
+        let dense: Grid<char> = "#.#\n##.\n".parse().unwrap();
+        let grid = SparseGrid::from_grid(&dense, |&c| c == '#');
+        assert_eq!(grid.len(), 4);
+        let mut ns = grid.neighbors((0, 1).into());
+        ns.sort();
+        assert_eq!(ns, vec![(0, 0).into(), (1, 1).into()]);
+        assert!(grid.neighbors((2, 0).into()).is_empty());
+    }
+
+    #[test]
+    fn sparse_dense_round_trip() {
+        let dense: Grid<char> = "..#.\n.##.\n....\n".parse().unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |&c| c == '#');
+        assert_eq!(sparse.render('.'), ".#\n##");
+        assert_eq!(sparse.to_grid('.'), ".#\n##".parse().unwrap());
+        let shifted: SparseGrid<char> = sparse
+            .iter()
+            .map(|(p, &c)| (p + (-5, 7).into(), c))
+            .collect();
+        assert_eq!(shifted.render(' '), " #\n##");
+    }
+}
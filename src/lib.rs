@@ -1,4 +1,16 @@
-#![feature(let_chains)]
-#![feature(closure_lifetime_binder)]
+pub mod answers;
+pub mod bench;
+pub mod bfs;
+pub mod cycle;
+pub mod days;
 pub mod dijkstra;
+pub mod error;
+pub mod frames;
+pub mod graph;
 pub mod grid;
+pub mod memo;
+pub mod params;
+pub mod parse;
+pub mod render;
+pub mod solution;
+pub mod vector;
@@ -0,0 +1,204 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+// A map a Memo can keep its results in
+pub trait Cache<K, V>: Default {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn insert(&mut self, key: K, value: V);
+    fn len(&self) -> usize;
+    fn clear(&mut self);
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq, V> Cache<K, V> for HashMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+}
+
+impl<K: Ord, V> Cache<K, V> for BTreeMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        BTreeMap::insert(self, key, value);
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        BTreeMap::clear(self)
+    }
+}
+
+// How well a Memo has been doing
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    // Calls answered from the cache
+    pub hits: usize,
+    // Calls that had to be computed
+    pub misses: usize,
+    // Results thrown away to stay within the size limit
+    pub evicted: usize,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            calls => self.hits as f64 / calls as f64,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evicted",
+            self.hits,
+            self.misses,
+            100.0 * self.hit_rate(),
+            self.evicted
+        )
+    }
+}
+
+// Remembers the results of a recursive function. The function body is passed to get as a closure
+// that is handed the memo back, so that its recursive calls go through the memo too:
+//
+//     fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//         if n < 2 {
+//             return n;
+//         }
+//         memo.get(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+//     }
+//
+// Results are kept in a HashMap unless another Cache is given, e.g. Memo<K, V, BTreeMap<K, V>> for
+// keys that can be ordered but not hashed.
+pub struct Memo<K, V, M = HashMap<K, V>> {
+    cache: M,
+    limit: Option<usize>,
+    stats: Stats,
+    _entries: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V: Clone, M: Cache<K, V>> Memo<K, V, M> {
+    pub fn new() -> Self {
+        Memo {
+            cache: M::default(),
+            limit: None,
+            stats: Stats::default(),
+            _entries: PhantomData,
+        }
+    }
+
+    // Keep at most limit results. When the cache is full, the whole cache is dropped before the next
+    // result goes in and is then refilled, so memory stays bounded at the cost of recomputing.
+    pub fn with_limit(limit: usize) -> Self {
+        Memo {
+            limit: Some(limit.max(1)),
+            ..Self::new()
+        }
+    }
+
+    // The result for key, computing it with f if it isn't known yet
+    pub fn get<F: FnOnce(&mut Self) -> V>(&mut self, key: K, f: F) -> V {
+        if let Some(v) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return v.clone();
+        }
+        self.stats.misses += 1;
+        let v = f(self);
+        if self.limit.is_some_and(|limit| self.cache.len() >= limit) {
+            self.stats.evicted += self.cache.len();
+            self.cache.clear();
+        }
+        self.cache.insert(key, v.clone());
+        v
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K, V: Clone, M: Cache<K, V>> Default for Memo<K, V, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib<M: Cache<u64, u64>>(n: u64, memo: &mut Memo<u64, u64, M>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+    }
+
+    #[test]
+    fn remembers_results() {
+        let mut memo = Memo::<u64, u64>::new();
+        assert_eq!(fib(50, &mut memo), 12586269025);
+        assert_eq!(memo.len(), 49);
+        // Every fib(n) for 2 <= n <= 50 is computed once, and all but fib(50) and fib(49) are
+        // looked up again by the call two above them
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 47,
+                misses: 49,
+                evicted: 0
+            }
+        );
+        fib(50, &mut memo);
+        assert_eq!(memo.stats().hits, 48);
+    }
+
+    #[test]
+    fn ordered_backend() {
+        let mut memo = Memo::<u64, u64, BTreeMap<u64, u64>>::new();
+        assert_eq!(fib(30, &mut memo), 832040);
+        assert_eq!(memo.stats().misses, 29);
+    }
+
+    #[test]
+    fn size_limit() {
+        let mut memo = Memo::<u64, u64>::with_limit(10);
+        assert_eq!(fib(50, &mut memo), 12586269025);
+        assert!(memo.len() <= 10);
+        assert_eq!(memo.stats().evicted + memo.len(), memo.stats().misses);
+    }
+}
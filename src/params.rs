@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::AocError;
+
+// Which defaults the puzzle constants take: the examples often use a smaller grid or fewer steps
+// than the real input
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Profile {
+    Example,
+    #[default]
+    Real,
+}
+
+impl Profile {
+    // Example inputs are the ones with "example" in their name, e.g. input/14_example
+    pub fn for_input(name: &str) -> Self {
+        if name.contains("example") {
+            Profile::Example
+        } else {
+            Profile::Real
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Profile::Example => write!(f, "example"),
+            Profile::Real => write!(f, "real"),
+        }
+    }
+}
+
+// A puzzle constant a day declares, with its default in each profile
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub example: &'static str,
+    pub real: &'static str,
+}
+
+impl Param {
+    // A constant that is the same for the examples and the real input
+    pub const fn new(name: &'static str, value: &'static str) -> Self {
+        Param {
+            name,
+            example: value,
+            real: value,
+        }
+    }
+
+    pub const fn profiles(name: &'static str, example: &'static str, real: &'static str) -> Self {
+        Param {
+            name,
+            example,
+            real,
+        }
+    }
+
+    pub fn default_for(&self, profile: Profile) -> &'static str {
+        match profile {
+            Profile::Example => self.example,
+            Profile::Real => self.real,
+        }
+    }
+}
+
+// The profile to take defaults from, and values given by name that replace them, e.g. from
+// `--param w=11 --param h=7`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Settings {
+    pub profile: Profile,
+    pub overrides: Vec<(String, String)>,
+}
+
+impl Settings {
+    pub fn new(profile: Profile) -> Self {
+        Settings {
+            profile,
+            overrides: Vec::new(),
+        }
+    }
+}
+
+// Parse a "name=value" override
+pub fn parse_override(s: &str) -> Result<(String, String), AocError> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(AocError::InvalidParam(format!("{s} (expected name=value)"))),
+    }
+}
+
+// The values of a day's parameters for one run
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<&'static str, String>,
+}
+
+impl Params {
+    // The defaults of the declared parameters in settings' profile, with its overrides applied.
+    // Overriding a parameter the day doesn't declare is an error, so typos don't go unnoticed.
+    pub fn resolve(declared: &[Param], settings: &Settings) -> Result<Self, AocError> {
+        let mut values: BTreeMap<_, _> = declared
+            .iter()
+            .map(|p| (p.name, p.default_for(settings.profile).to_string()))
+            .collect();
+        for (name, value) in &settings.overrides {
+            match values.get_mut(name.as_str()) {
+                Some(v) => *v = value.clone(),
+                None => {
+                    let known: Vec<_> = declared.iter().map(|p| p.name).collect();
+                    return Err(AocError::InvalidParam(format!(
+                        "unknown parameter {name} (expected one of: {})",
+                        known.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(Params { values })
+    }
+
+    pub fn get<T>(&self, name: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .values
+            .get(name)
+            .ok_or_else(|| AocError::InvalidParam(format!("{name} is not declared")))?;
+        value
+            .parse()
+            .map_err(|e| AocError::InvalidParam(format!("{name}={value}: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[Param::profiles("w", "11", "101"), Param::new("c", "t")];
+
+    #[test]
+    fn profile_defaults() {
+        assert_eq!(Profile::for_input("input/14_example"), Profile::Example);
+        assert_eq!(Profile::for_input("input/14"), Profile::Real);
+        let example = Params::resolve(PARAMS, &Settings::new(Profile::Example)).unwrap();
+        assert_eq!(example.get::<usize>("w").unwrap(), 11);
+        assert_eq!(example.get::<char>("c").unwrap(), 't');
+        let real = Params::resolve(PARAMS, &Settings::default()).unwrap();
+        assert_eq!(real.get::<usize>("w").unwrap(), 101);
+    }
+
+    #[test]
+    fn overrides() {
+        let settings = Settings {
+            profile: Profile::Example,
+            overrides: vec![parse_override("w=7").unwrap()],
+        };
+        let params = Params::resolve(PARAMS, &settings).unwrap();
+        assert_eq!(params.get::<usize>("w").unwrap(), 7);
+        assert!(parse_override("w").is_err());
+
+        let typo = Settings {
+            profile: Profile::Real,
+            overrides: vec![("x".to_string(), "1".to_string())],
+        };
+        assert!(Params::resolve(PARAMS, &typo).is_err());
+        assert!(params.get::<usize>("c").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, multispace0, space0},
+    combinator::{all_consuming, map, map_res, opt, recognize},
+    error::{ErrorKind, ParseError as _},
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+use num::Num;
+
+use crate::vector::Vector2;
+
+// Nom parsers for the pieces that keep turning up in puzzle inputs, and parse_all to run one over a
+// whole input with an error that points at the offending line.
+
+// A decimal integer of any type, with an optional minus sign
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+// Integers separated by sep, e.g. "1 2 3" or "4,5,6"
+pub fn integers<'a, T: FromStr>(sep: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(tag(sep), integer)
+}
+
+// A coordinate pair "x,y"
+pub fn point<T: Num + FromStr>(input: &str) -> IResult<&str, Vector2<T>> {
+    map(separated_pair(integer, char(','), integer), |(x, y)| {
+        Vector2 { x, y }
+    })(input)
+}
+
+// One or more lines, each parsed by line, up to the end of the input or of the section. Unlike
+// separated_list1, a line that fails to parse is an error rather than the end of the list, so the
+// error points into that line.
+pub fn lines<'a, O, F>(mut line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    let is_line_end = |s: &'a str| line_ending::<_, nom::error::Error<_>>(s);
+    move |mut input| {
+        let mut out = Vec::new();
+        loop {
+            let (rest, o) = line.parse(input)?;
+            out.push(o);
+            match is_line_end(rest) {
+                Ok((next, _)) if !next.trim_end().is_empty() && is_line_end(next).is_err() => {
+                    input = next
+                }
+                _ => return Ok((rest, out)),
+            }
+        }
+    }
+}
+
+// The empty line separating two sections of an input, along with the end of the line before it
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    map(pair(line_ending, line_ending), |_| ())(input)
+}
+
+// A "key: value" pair
+pub fn key_value<'a, K, V, F, G>(
+    key: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    F: Parser<&'a str, K, nom::error::Error<&'a str>>,
+    G: Parser<&'a str, V, nom::error::Error<&'a str>>,
+{
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+// The value of a header with a known name, e.g. header("Register A", integer) for "Register A: 729"
+pub fn header<'a, V, G>(name: &'a str, value: G) -> impl FnMut(&'a str) -> IResult<&'a str, V>
+where
+    G: Parser<&'a str, V, nom::error::Error<&'a str>>,
+{
+    preceded(tuple((tag(name), char(':'), space0)), value)
+}
+
+// Where and why an input failed to parse
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based, counting characters
+    pub line: usize,
+    pub column: usize,
+    // The line the error is on, without its line ending
+    pub text: String,
+    pub kind: ErrorKind,
+}
+
+impl ParseError {
+    // Locate the error at remaining, the unparsed tail of input
+    fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let offset = input.len() - remaining.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let text = input[line_start..].lines().next().unwrap_or("");
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            ErrorKind::Eof => "unexpected text".to_string(),
+            kind => format!("expected {}", kind.description()),
+        };
+        writeln!(
+            f,
+            "Invalid input at line {}, column {} ({reason}):",
+            self.line, self.column
+        )?;
+        writeln!(f, "{}", self.text)?;
+        write!(f, "{:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+// Parse the whole of input, allowing only trailing whitespace after what the parser consumes
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    match all_consuming(terminated(parser, multispace0))(input) {
+        Ok((_, out)) => Ok(out),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            Err(ParseError::new(input, e.input, e.code))
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            let e = nom::error::Error::from_error_kind(&input[input.len()..], ErrorKind::Eof);
+            Err(ParseError::new(input, e.input, e.code))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::alpha1;
+
+    #[test]
+    fn numbers_and_points() {
+        assert_eq!(parse_all(integer::<i32>, "-42\n"), Ok(-42));
+        assert!(parse_all(integer::<u32>, "-42").is_err());
+        assert_eq!(
+            parse_all(integers::<u8>(", "), "1, 2, 3"),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            parse_all(lines(point::<isize>), "1,2\n-3,4\n"),
+            Ok(vec![Vector2 { x: 1, y: 2 }, Vector2 { x: -3, y: 4 }])
+        );
+    }
+
+    #[test]
+    fn sections_and_headers() {
+        let input = "Name: x\nsize: 3\n\nab\ncd\n";
+        let parser = separated_pair(
+            pair(
+                terminated(header("Name", alpha1), line_ending),
+                key_value(alpha1, integer::<usize>),
+            ),
+            blank_line,
+            lines(alpha1),
+        );
+        assert_eq!(
+            parse_all(parser, input),
+            Ok((("x", ("size", 3)), vec!["ab", "cd"]))
+        );
+    }
+
+    #[test]
+    fn error_location() {
+        let e = parse_all(lines(point::<usize>), "1,2\n3,x\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "3,x"));
+        assert_eq!(
+            e.to_string(),
+            "Invalid input at line 2, column 3 (expected Digit):\n3,x\n  ^"
+        );
+        let e = parse_all(lines(point::<usize>), "1,2\n3,4 5,6\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 5));
+        assert!(e.to_string().contains("unexpected text"));
+    }
+}
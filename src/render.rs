@@ -0,0 +1,228 @@
+use std::fmt;
+use std::hash::Hash;
+use std::io::IsTerminal;
+
+use crate::dijkstra::{Cost, DijkstraPath};
+use crate::grid::{to_signed, CardinalDirection, Grid, GridIndex};
+
+// The ANSI foreground colours used for overlays
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Cell {
+    c: char,
+    color: Option<Color>,
+}
+
+// A grid drawn as characters, with overlays marking points, paths and directions on top. It is
+// printed in colour to a terminal and as plain text anywhere else.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    cells: Grid<Cell>,
+}
+
+impl Canvas {
+    // Draw every cell of grid as the character f gives for it
+    pub fn new<T, F: Fn(&T) -> char>(grid: &Grid<T>, f: F) -> Self {
+        Canvas {
+            cells: grid.map(|t| Cell {
+                c: f(t),
+                color: None,
+            }),
+        }
+    }
+
+    // Colour the cells at points, also replacing their character if c is given. Points outside
+    // the canvas are ignored.
+    pub fn mark<P: GridIndex>(
+        &mut self,
+        points: impl IntoIterator<Item = P>,
+        c: Option<char>,
+        color: Color,
+    ) -> &mut Self {
+        for p in points {
+            if let Some(cell) = self.cells.get_mut(p) {
+                cell.c = c.unwrap_or(cell.c);
+                cell.color = Some(color);
+            }
+        }
+        self
+    }
+
+    // Draw an arrow in each given cell
+    pub fn arrows<P: GridIndex>(
+        &mut self,
+        arrows: impl IntoIterator<Item = (P, CardinalDirection)>,
+        color: Color,
+    ) -> &mut Self {
+        for (p, d) in arrows {
+            self.mark([p], Some(d.into()), color);
+        }
+        self
+    }
+
+    // Draw a path as arrows from each node towards the next one, pos giving the cell a node is in.
+    // Nodes that share a cell with the next one, such as a turn on the spot, are only coloured.
+    pub fn path<T, C, P, F>(&mut self, path: &DijkstraPath<T, C>, pos: F, color: Color) -> &mut Self
+    where
+        T: Hash + Eq + Copy,
+        C: Cost,
+        P: GridIndex,
+        F: Fn(&T) -> P,
+    {
+        let cells: Vec<P> = path.path.iter().map(|n| pos(&n.pos)).collect();
+        self.mark(cells.iter().copied(), None, color);
+        let steps = cells.windows(2).filter_map(|w| {
+            let (a, b) = (w[0].to_upoint()?, w[1].to_upoint()?);
+            let delta = to_signed(b) - to_signed(a);
+            let d = CardinalDirection::ALL
+                .into_iter()
+                .find(|d| d.delta() == delta)?;
+            Some((a, d))
+        });
+        self.arrows(steps.collect::<Vec<_>>(), color)
+    }
+
+    // The canvas as text, without colours
+    pub fn plain(&self) -> String {
+        self.to_string()
+    }
+
+    // The canvas as text with ANSI colour codes
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for (y, row) in self.cells.rows().enumerate() {
+            if y > 0 {
+                out.push('\n');
+            }
+            let mut current = None;
+            for cell in row {
+                if cell.color != current {
+                    match cell.color {
+                        Some(color) => out.push_str(&format!("\x1b[{}m", color.code())),
+                        None => out.push_str("\x1b[0m"),
+                    }
+                    current = cell.color;
+                }
+                out.push(cell.c);
+            }
+            if current.is_some() {
+                out.push_str("\x1b[0m");
+            }
+        }
+        out
+    }
+
+    // Print to stdout, in colour if it is a terminal
+    pub fn print(&self) {
+        if use_color(&std::io::stdout()) {
+            println!("{}", self.ansi());
+        } else {
+            println!("{}", self.plain());
+        }
+    }
+
+    // Print to stderr, in colour if it is a terminal
+    pub fn eprint(&self) {
+        if use_color(&std::io::stderr()) {
+            eprintln!("{}", self.ansi());
+        } else {
+            eprintln!("{}", self.plain());
+        }
+    }
+}
+
+impl From<&Grid<char>> for Canvas {
+    fn from(grid: &Grid<char>) -> Self {
+        Canvas::new(grid, |&c| c)
+    }
+}
+
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells.map(|cell| cell.c))
+    }
+}
+
+// Colours are only for terminals, and can be turned off with NO_COLOR (https://no-color.org)
+fn use_color(stream: &impl IsTerminal) -> bool {
+    stream.is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::DijkstraNode;
+    use crate::grid::UPoint;
+    use nonempty::NonEmpty;
+
+    fn canvas() -> Canvas {
+        let grid: Grid<char> = "....\n.##.\n....\n".parse().unwrap();
+        Canvas::from(&grid)
+    }
+
+    #[test]
+    fn overlays() {
+        let mut c = canvas();
+        c.mark([UPoint { x: 0, y: 0 }], Some('S'), Color::Red)
+            .arrows(
+                [(UPoint { x: 3, y: 2 }, CardinalDirection::North)],
+                Color::Blue,
+            );
+        assert_eq!(c.plain(), "S...\n.##.\n...^");
+    }
+
+    #[test]
+    fn path_arrows() {
+        let nodes = [(0, 0), (0, 0), (1, 0), (2, 0), (3, 0), (3, 1)]
+            .into_iter()
+            .enumerate()
+            .map(|(dist, (x, y))| DijkstraNode {
+                pos: UPoint { x, y },
+                dist,
+            })
+            .collect();
+        let path = DijkstraPath {
+            path: NonEmpty::from_vec(nodes).unwrap(),
+        };
+        let mut c = canvas();
+        c.path(&path, |p| *p, Color::Green);
+        assert_eq!(c.plain(), ">>>v\n.##.\n....");
+    }
+
+    #[test]
+    fn ansi_colours() {
+        let mut c = canvas();
+        c.mark(
+            [UPoint { x: 1, y: 1 }, UPoint { x: 2, y: 1 }],
+            None,
+            Color::Red,
+        );
+        assert_eq!(c.ansi(), "....\n.\x1b[31m##\x1b[0m.\n....");
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::AocError;
+use crate::frames::Recorder;
+use crate::params::{Param, Params, Profile, Settings};
+use crate::render::Canvas;
+
+// An answer to one part of a puzzle. Answers are shown as text, and written to JSON records as
+// numbers if they are numbers and as strings otherwise.
+pub trait Answer: Display {
+    fn to_json(&self) -> Value {
+        Value::from(self.to_string())
+    }
+}
+
+macro_rules! impl_numeric_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn to_json(&self) -> Value {
+                Value::from(*self)
+            }
+        })*
+    };
+}
+
+impl_numeric_answer!(i32, i64, isize, u32, u64, usize);
+
+impl Answer for String {}
+
+// A day's puzzle: parse the input once, then answer both parts from the parsed input.
+// The input is borrowed for as long as the parsed form lives, so days can parse into &str slices
+// of the puzzle text.
+pub trait Solution {
+    type Input<'a>;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    // The puzzle constants the day reads from the Params given to parse, such as the size of a
+    // room that is smaller in the examples
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, AocError>;
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Answer1, AocError>;
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Answer2, AocError>;
+
+    // Phrase an answer as a sentence for the human-readable output
+    fn report1(answer: &Self::Answer1) -> String {
+        format!("Part 1: {answer}")
+    }
+    fn report2(answer: &Self::Answer2) -> String {
+        format!("Part 2: {answer}")
+    }
+
+    // Details of an answer beyond its value, added to its `aoc run --format json` record, such as
+    // the sequence of changes that maximizes the bananas on day 22
+    fn extras1(_answer: &Self::Answer1) -> Option<Value> {
+        None
+    }
+
+    fn extras2(_answer: &Self::Answer2) -> Option<Value> {
+        None
+    }
+
+    // Parse an example with the example values of the parameters
+    fn parse_example(input: &str) -> Result<Self::Input<'_>, AocError> {
+        let params = Params::resolve(Self::PARAMS, &Settings::new(Profile::Example))?;
+        Self::parse(input, &params)
+    }
+
+    // A picture of the puzzle for `aoc run --show`, such as a maze with its best path drawn on it.
+    // Most days have nothing worth drawing.
+    fn draw(_input: &Self::Input<'_>) -> Result<Option<Canvas>, AocError> {
+        Ok(None)
+    }
+
+    // Replay the puzzle's simulation step by step into frames, for `aoc run --record`. Days that
+    // don't simulate anything record nothing.
+    fn animate(_input: &Self::Input<'_>, _frames: &mut Recorder) -> Result<(), AocError> {
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl std::str::FromStr for Part {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part: {s} (expected 1 or 2)")),
+        }
+    }
+}
+
+// The result of running one part of a day, with the answer already rendered to text and to JSON
+#[derive(Clone, Debug)]
+pub struct PartOutcome {
+    pub part: Part,
+    pub answer: String,
+    pub json: Value,
+    pub report: String,
+    pub extras: Option<Value>,
+    pub elapsed: Duration,
+}
+
+impl PartOutcome {
+    // The answer as a record for scripts
+    pub fn record(&self, day: u8) -> Record {
+        Record {
+            day,
+            part: match self.part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            answer: self.json.clone(),
+            elapsed_ns: self.elapsed.as_nanos() as u64,
+            extras: self.extras.clone(),
+        }
+    }
+}
+
+// One answer as printed by `aoc run --format json`, one record per line
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Value,
+    pub elapsed_ns: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extras: Option<Value>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub parse_time: Duration,
+    pub parts: Vec<PartOutcome>,
+}
+
+// Parse the input and run the requested parts of a solution, timing each step
+pub fn run<S: Solution>(
+    input: &str,
+    settings: &Settings,
+    parts: &[Part],
+) -> Result<Outcome, AocError> {
+    let params = Params::resolve(S::PARAMS, settings)?;
+    let t = Instant::now();
+    let parsed = S::parse(input, &params)?;
+    let parse_time = t.elapsed();
+
+    let mut out = Vec::new();
+    for &part in parts {
+        let t = Instant::now();
+        let (answer, json, report, extras, elapsed) = match part {
+            Part::One => {
+                let a = S::part1(&parsed)?;
+                let elapsed = t.elapsed();
+                (
+                    a.to_string(),
+                    a.to_json(),
+                    S::report1(&a),
+                    S::extras1(&a),
+                    elapsed,
+                )
+            }
+            Part::Two => {
+                let a = S::part2(&parsed)?;
+                let elapsed = t.elapsed();
+                (
+                    a.to_string(),
+                    a.to_json(),
+                    S::report2(&a),
+                    S::extras2(&a),
+                    elapsed,
+                )
+            }
+        };
+        out.push(PartOutcome {
+            part,
+            answer,
+            json,
+            report,
+            extras,
+            elapsed,
+        });
+    }
+    Ok(Outcome {
+        parse_time,
+        parts: out,
+    })
+}
+
+pub type Runner = fn(&str, &Settings, &[Part]) -> Result<Outcome, AocError>;
+
+// Parse the input and draw it, if the solution knows how
+pub fn draw<S: Solution>(input: &str, settings: &Settings) -> Result<Option<Canvas>, AocError> {
+    S::draw(&S::parse(input, &Params::resolve(S::PARAMS, settings)?)?)
+}
+
+pub type Drawer = fn(&str, &Settings) -> Result<Option<Canvas>, AocError>;
+
+// Parse the input and record its simulation
+pub fn animate<S: Solution>(
+    input: &str,
+    settings: &Settings,
+    frames: &mut Recorder,
+) -> Result<(), AocError> {
+    S::animate(
+        &S::parse(input, &Params::resolve(S::PARAMS, settings)?)?,
+        frames,
+    )
+}
+
+pub type Animator = fn(&str, &Settings, &mut Recorder) -> Result<(), AocError>;
+
+// A day registered with the runner
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub day: u8,
+    pub run: Runner,
+    pub draw: Drawer,
+    pub animate: Animator,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome<A: Answer>(answer: A, extras: Option<Value>) -> PartOutcome {
+        PartOutcome {
+            part: Part::Two,
+            answer: answer.to_string(),
+            json: answer.to_json(),
+            report: format!("The answer is {answer}"),
+            extras,
+            elapsed: Duration::from_micros(5),
+        }
+    }
+
+    #[test]
+    fn json_records() {
+        let record = outcome(154115708116294u64, None).record(21);
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"day":21,"part":2,"answer":154115708116294,"elapsed_ns":5000}"#
+        );
+        let extras = serde_json::json!({ "sequence": [-2, 1, -1, 3] });
+        let record = outcome("co,de,ka,ta".to_string(), Some(extras.clone())).record(23);
+        assert_eq!(record.answer, Value::from("co,de,ka,ta"));
+        assert_eq!(record.extras, Some(extras));
+        // Text answers stay text, even when they look like numbers
+        let record = outcome("0012".to_string(), None).record(17);
+        assert_eq!(record.answer, Value::from("0012"));
+    }
+}
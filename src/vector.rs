@@ -0,0 +1,253 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num::traits::{CheckedAdd, CheckedSub};
+
+// A point or displacement on a plane, such as a cell of a grid or a step between two cells.
+// Points are ordered by x, then y.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+// The integer types a Vector2 can be made of, with their distances measured as usize
+pub trait Component: Copy {
+    // |self|
+    fn magnitude(self) -> usize;
+    // |self - other|, without overflowing
+    fn distance(self, other: Self) -> usize;
+}
+
+macro_rules! impl_component {
+    ($($t:ty),*) => {
+        $(impl Component for $t {
+            fn magnitude(self) -> usize {
+                self.abs_diff(0) as usize
+            }
+
+            fn distance(self, other: Self) -> usize {
+                self.abs_diff(other) as usize
+            }
+        })*
+    };
+}
+
+impl_component!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T> Vector2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+impl<T: CheckedAdd + CheckedSub> Vector2<T> {
+    // self + rhs, unless a component overflows, e.g. a usize point stepping off the top of a grid
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Vector2 {
+            x: self.x.checked_add(&rhs.x)?,
+            y: self.y.checked_add(&rhs.y)?,
+        })
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(Vector2 {
+            x: self.x.checked_sub(&rhs.x)?,
+            y: self.y.checked_sub(&rhs.y)?,
+        })
+    }
+}
+
+impl<T: Component> Vector2<T> {
+    // The number of orthogonal steps from the origin
+    pub fn manhattan_norm(&self) -> usize {
+        self.x.magnitude() + self.y.magnitude()
+    }
+
+    // The number of steps from the origin when diagonal steps are allowed
+    pub fn chebyshev_norm(&self) -> usize {
+        self.x.magnitude().max(self.y.magnitude())
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> usize {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> usize {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Vector2<T> {
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    // The z component of the cross product: positive when other is clockwise of self on a grid
+    // with y increasing downwards, and zero when the two are parallel
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Neg<Output = T>> Vector2<T> {
+    // Rotate about the origin by the given number of quarter turns; positive is clockwise with y
+    // increasing downwards, as for CardinalDirection::rotate
+    pub fn rotate(self, quarter_turns: isize) -> Self {
+        let Vector2 { x, y } = self;
+        match quarter_turns.rem_euclid(4) {
+            0 => Vector2 { x, y },
+            1 => Vector2 { x: -y, y: x },
+            2 => Vector2 { x: -x, y: -y },
+            _ => Vector2 { x: y, y: -x },
+        }
+    }
+
+    pub fn clockwise(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn counterclockwise(self) -> Self {
+        self.rotate(-1)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector2<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Vector2 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector2<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Vector2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vector2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vector2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+// Scaling by a number of steps
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vector2<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Vector2 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector2<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Vector2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T> From<(T, T)> for Vector2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+impl<T> From<Vector2<T>> for (T, T) {
+    fn from(v: Vector2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl<T> From<[T; 2]> for Vector2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+impl<T> From<Vector2<T>> for [T; 2] {
+    fn from(v: Vector2<T>) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Vector2::new(3isize, -2);
+        assert_eq!(p + (1, 1).into(), Vector2::new(4, -1));
+        assert_eq!(p - (1, 1).into(), Vector2::new(2, -3));
+        assert_eq!(p * 3, Vector2::new(9, -6));
+        assert_eq!(-p, Vector2::new(-3, 2));
+        p += Vector2::new(-3, 2);
+        assert_eq!(p, Vector2::default());
+        p -= Vector2::new(1, 0);
+        assert_eq!(<(isize, isize)>::from(p), (-1, 0));
+
+        let u = Vector2::new(0usize, 5);
+        assert_eq!(u.checked_sub(&(1, 0).into()), None);
+        assert_eq!(u.checked_add(&(1, 0).into()), Some((1, 5).into()));
+        assert_eq!(Vector2::new(i8::MAX, 0).checked_add(&(1, 0).into()), None);
+        assert_eq!(u.to_string(), "(0,5)");
+        assert!(Vector2::new(0, 9) < Vector2::new(1, 0));
+    }
+
+    #[test]
+    fn norms_and_distances() {
+        let v = Vector2::new(-3i8, 4);
+        assert_eq!(v.manhattan_norm(), 7);
+        assert_eq!(v.chebyshev_norm(), 4);
+        let (a, b) = (Vector2::new(2usize, 9), Vector2::new(7usize, 1));
+        assert_eq!(a.manhattan_distance(&b), 13);
+        assert_eq!(b.chebyshev_distance(&a), 8);
+        // Distances across the whole range of the type don't overflow
+        assert_eq!(
+            Vector2::new(i8::MIN, 0).manhattan_distance(&(i8::MAX, 0).into()),
+            255
+        );
+    }
+
+    #[test]
+    fn products_and_rotations() {
+        let east = Vector2::new(1isize, 0);
+        let south = Vector2::new(0isize, 1);
+        assert_eq!(east.dot(&south), 0);
+        assert_eq!(east.dot(&(east * 2)), 2);
+        assert_eq!(east.cross(&south), 1);
+        assert_eq!(south.cross(&east), -1);
+        assert_eq!(east.clockwise(), south);
+        assert_eq!(south.counterclockwise(), east);
+        assert_eq!(east.rotate(2), -east);
+        assert_eq!(east.rotate(-3), south);
+        assert_eq!(Vector2::new(2, 5).rotate(4), Vector2::new(2, 5));
+    }
+}